@@ -1,3 +1,4 @@
+// Generated from matrix.toml, do not edit.
 #[cfg(test)]
 mod test {
     use aarch64_applesilicon::*;
@@ -5,11 +6,11 @@ mod test {
 
     #[test]
     fn random() {
-        tests_instruction_from_file(RANDOM_INSTRUCTION_FILE, parse_default)
+        tests_instruction_from_file(RANDOM_INSTRUCTION_FILE, LITTLE_ENDIAN, parse_default)
     }
     #[test]
     #[ignore]
     fn random_big() {
-        tests_instruction_from_file(RANDOM_BIG_INSTRUCTION_FILE, parse_default)
+        tests_instruction_from_file(RANDOM_BIG_INSTRUCTION_FILE, LITTLE_ENDIAN, parse_default)
     }
 }
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod test {
+    use aarch64_lib::*;
+
+    #[test]
+    fn random() {
+        cross_check_from_file(
+            RANDOM_INSTRUCTION_FILE,
+            aarch64::parse_default,
+            aarch64be::parse_default,
+        )
+    }
+    #[test]
+    fn swapped_instruction_fetch() {
+        cross_check_swapped_from_file(
+            RANDOM_INSTRUCTION_FILE,
+            aarch64::parse_default,
+            aarch64be::parse_default,
+        )
+    }
+    #[test]
+    fn sweep() {
+        cross_check_random(
+            0xa64,
+            0x10000,
+            aarch64::parse_default,
+            aarch64be::parse_default,
+        )
+    }
+    #[test]
+    #[ignore]
+    fn random_big() {
+        cross_check_from_file(
+            RANDOM_BIG_INSTRUCTION_FILE,
+            aarch64::parse_default,
+            aarch64be::parse_default,
+        )
+    }
+}
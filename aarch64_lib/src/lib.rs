@@ -1,7 +1,39 @@
 use std::io::Read;
 
+use common_lib::XorShift;
 use serde::Deserialize;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endian {
+    Little,
+    Big,
+}
+impl Endian {
+    fn u32_bytes(&self, value: u32) -> [u8; 4] {
+        match self {
+            Endian::Little => value.to_le_bytes(),
+            Endian::Big => value.to_be_bytes(),
+        }
+    }
+}
+
+/// AArch64 always fetches instructions in little endian, the big endian
+/// variant only affects the data accesses, eg: literal pool loads.
+///
+/// Only the instruction byte order is modelled, the disassembly only shows
+/// the literal address, not its value, so the data byte order can't be
+/// observed by the tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteOrder {
+    pub instruction: Endian,
+}
+pub const LITTLE_ENDIAN: ByteOrder = ByteOrder {
+    instruction: Endian::Little,
+};
+pub const BIG_ENDIAN: ByteOrder = ByteOrder {
+    instruction: Endian::Little,
+};
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Instruction {
     pub addr: u64,
@@ -9,8 +41,8 @@ pub struct Instruction {
     pub result: String,
 }
 impl Instruction {
-    fn tokens(&self) -> [u8; 4] {
-        self.token.to_le_bytes()
+    fn tokens(&self, byte_order: ByteOrder) -> [u8; 4] {
+        byte_order.instruction.u32_bytes(self.token)
     }
 }
 
@@ -19,21 +51,25 @@ impl<R: Read> Iterator for TestsFromFile<R> {
     type Item = csv::Result<Instruction>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().map(|test| test.map(Instruction::from))
+        self.0.next()
     }
 }
 
 pub const RANDOM_INSTRUCTION_FILE: &str = "../assets/aarch64/random.csv";
 pub const RANDOM_BIG_INSTRUCTION_FILE: &str = "../assets/aarch64/random_big.csv";
+pub const LITERAL_INSTRUCTION_FILE: &str = "../assets/aarch64/literal.csv";
 pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
     TestsFromFile(csv::Reader::from_reader(file).into_deserialize::<Instruction>())
 }
 
-pub fn tests_instruction_from_file(file: &str, parse: fn(&[u8], u64) -> Option<(u64, String)>) {
+pub type ParseFn = fn(&[u8], u64) -> Option<(u64, String)>;
+
+pub fn tests_instruction_from_file(file: &str, byte_order: ByteOrder, parse: ParseFn) {
     let test_file = std::fs::File::open(file).unwrap();
     let instructions = tests_from_file(test_file);
     for instruction in instructions.map(Result::unwrap) {
-        let Some((next_addr, result)) = parse(&instruction.tokens(), instruction.addr) else {
+        let tokens = instruction.tokens(byte_order);
+        let Some((next_addr, result)) = parse(&tokens, instruction.addr) else {
                 panic!(
                     "Unable to parse the {:x?} with expected output `{}`",
                     &instruction.token,
@@ -44,3 +80,79 @@ pub fn tests_instruction_from_file(file: &str, parse: fn(&[u8], u64) -> Option<(
         assert_eq!(next_addr, instruction.addr + 4);
     }
 }
+
+/// Feed the same instruction to the little and big endian crates, each with its
+/// own byte order, and check that both produce the same disassembly and length,
+/// returns if it was decoded.
+fn cross_check_instruction(
+    instruction: &Instruction,
+    parse_le: ParseFn,
+    parse_be: ParseFn,
+) -> bool {
+    let le_result = parse_le(&instruction.tokens(LITTLE_ENDIAN), instruction.addr);
+    let be_result = parse_be(&instruction.tokens(BIG_ENDIAN), instruction.addr);
+    assert_eq!(
+        le_result, be_result,
+        "little/big endian mismatch at token {:#010x} addr {:#x}",
+        instruction.token, instruction.addr
+    );
+    le_result.is_some()
+}
+
+pub fn cross_check_from_file(file: &str, parse_le: ParseFn, parse_be: ParseFn) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        assert!(
+            cross_check_instruction(&instruction, parse_le, parse_be),
+            "Unable to parse the {:#010x} with expected output `{}`",
+            instruction.token,
+            instruction.result,
+        );
+    }
+}
+
+/// Feed the instructions with the opposite byte order to the little and big
+/// endian crates, both fetch in little endian, so they need to decode the
+/// swapped word the same way, and not as the original instruction.
+pub fn cross_check_swapped_from_file(file: &str, parse_le: ParseFn, parse_be: ParseFn) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        // palindromes are the same in both byte orders
+        if instruction.token == instruction.token.swap_bytes() {
+            continue;
+        }
+        let swapped = instruction.token.to_be_bytes();
+        let le_result = parse_le(&swapped, instruction.addr);
+        let be_result = parse_be(&swapped, instruction.addr);
+        assert_eq!(
+            le_result, be_result,
+            "little/big endian mismatch at swapped token {:#010x}",
+            instruction.token
+        );
+        assert_ne!(
+            be_result.map(|(_, output)| output).as_deref(),
+            Some(instruction.result.as_str()),
+            "instruction {:#010x} was fetched in big endian",
+            instruction.token
+        );
+    }
+}
+
+pub const CROSS_CHECK_ADDR: u64 = 0x10000;
+pub fn cross_check_random(seed: u64, count: usize, parse_le: ParseFn, parse_be: ParseFn) {
+    let mut rng = XorShift::new(seed);
+    let mut decoded = 0;
+    for _ in 0..count {
+        let instruction = Instruction {
+            addr: CROSS_CHECK_ADDR,
+            token: rng.next_u32(),
+            result: String::new(),
+        };
+        if cross_check_instruction(&instruction, parse_le, parse_be) {
+            decoded += 1;
+        }
+    }
+    assert_ne!(decoded, 0, "no instruction was decoded by the sweep");
+}
@@ -1,3 +1,4 @@
+// Generated from matrix.toml, do not edit.
 #[cfg(test)]
 mod test {
     use aarch64::*;
@@ -5,11 +6,15 @@ mod test {
 
     #[test]
     fn random() {
-        tests_instruction_from_file(RANDOM_INSTRUCTION_FILE, parse_default)
+        tests_instruction_from_file(RANDOM_INSTRUCTION_FILE, LITTLE_ENDIAN, parse_default)
+    }
+    #[test]
+    fn literal() {
+        tests_instruction_from_file(LITERAL_INSTRUCTION_FILE, LITTLE_ENDIAN, parse_default)
     }
     #[test]
     #[ignore]
     fn random_big() {
-        tests_instruction_from_file(RANDOM_BIG_INSTRUCTION_FILE, parse_default)
+        tests_instruction_from_file(RANDOM_BIG_INSTRUCTION_FILE, LITTLE_ENDIAN, parse_default)
     }
 }
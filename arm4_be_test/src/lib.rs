@@ -1,3 +1,4 @@
+// Generated from matrix.toml, do not edit.
 #[cfg(test)]
 mod test {
     use arm4_be::*;
@@ -5,6 +6,6 @@ mod test {
 
     #[test]
     fn basic_instructions() {
-        tests_instruction_from_file(BASIS_INSTRUCTION_FILE, Version::V4, true, parse_arm, None);
+        tests_instruction_from_file(BASIS_INSTRUCTION_FILE, Version::V4, true, parse_arm, None)
     }
 }
@@ -1,3 +1,4 @@
+// Generated from matrix.toml, do not edit.
 #[cfg(test)]
 mod test {
     use arm5_be::*;
@@ -5,6 +6,6 @@ mod test {
 
     #[test]
     fn basic_instructions() {
-        tests_instruction_from_file(BASIS_INSTRUCTION_FILE, Version::V5, true, parse_arm, None);
+        tests_instruction_from_file(BASIS_INSTRUCTION_FILE, Version::V5, true, parse_arm, None)
     }
 }
@@ -1,3 +1,4 @@
+// Generated from matrix.toml, do not edit.
 #[cfg(test)]
 mod test {
     use arm5t_le::*;
@@ -11,6 +12,6 @@ mod test {
             false,
             parse_arm,
             Some(parse_thumb),
-        );
+        )
     }
 }
@@ -1,3 +1,4 @@
+// Generated from matrix.toml, do not edit.
 #[cfg(test)]
 mod test {
     use arm8_be::*;
@@ -11,6 +12,6 @@ mod test {
             true,
             parse_arm,
             Some(parse_thumb),
-        );
+        )
     }
 }
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod test {
+    use arm_lib::*;
+
+    #[test]
+    fn arm4() {
+        let be = Decoder {
+            parse_arm: arm4_be::parse_arm,
+            parse_thumb: None,
+        };
+        let le = Decoder {
+            parse_arm: arm4_le::parse_arm,
+            parse_thumb: None,
+        };
+        cross_check_from_file(BASIS_INSTRUCTION_FILE, Version::V4, be, le);
+        cross_check_sweep(0x4, 0x10000, be, le);
+    }
+
+    #[test]
+    fn arm4t() {
+        let be = Decoder {
+            parse_arm: arm4t_be::parse_arm,
+            parse_thumb: Some(arm4t_be::parse_thumb),
+        };
+        let le = Decoder {
+            parse_arm: arm4t_le::parse_arm,
+            parse_thumb: Some(arm4t_le::parse_thumb),
+        };
+        cross_check_from_file(BASIS_INSTRUCTION_FILE, Version::V4, be, le);
+        cross_check_sweep(0x4, 0x10000, be, le);
+    }
+
+    #[test]
+    fn arm5() {
+        let be = Decoder {
+            parse_arm: arm5_be::parse_arm,
+            parse_thumb: None,
+        };
+        let le = Decoder {
+            parse_arm: arm5_le::parse_arm,
+            parse_thumb: None,
+        };
+        cross_check_from_file(BASIS_INSTRUCTION_FILE, Version::V5, be, le);
+        cross_check_sweep(0x5, 0x10000, be, le);
+    }
+
+    #[test]
+    fn arm5t() {
+        let be = Decoder {
+            parse_arm: arm5t_be::parse_arm,
+            parse_thumb: Some(arm5t_be::parse_thumb),
+        };
+        let le = Decoder {
+            parse_arm: arm5t_le::parse_arm,
+            parse_thumb: Some(arm5t_le::parse_thumb),
+        };
+        cross_check_from_file(BASIS_INSTRUCTION_FILE, Version::V5, be, le);
+        cross_check_sweep(0x5, 0x10000, be, le);
+    }
+
+    #[test]
+    fn arm6() {
+        let be = Decoder {
+            parse_arm: arm6_be::parse_arm,
+            parse_thumb: Some(arm6_be::parse_thumb),
+        };
+        let le = Decoder {
+            parse_arm: arm6_le::parse_arm,
+            parse_thumb: Some(arm6_le::parse_thumb),
+        };
+        cross_check_from_file(BASIS_INSTRUCTION_FILE, Version::V6, be, le);
+        cross_check_sweep(0x6, 0x10000, be, le);
+    }
+
+    #[test]
+    fn arm7() {
+        let be = Decoder {
+            parse_arm: arm7_be::parse_arm,
+            parse_thumb: Some(arm7_be::parse_thumb),
+        };
+        let le = Decoder {
+            parse_arm: arm7_le::parse_arm,
+            parse_thumb: Some(arm7_le::parse_thumb),
+        };
+        cross_check_from_file(BASIS_INSTRUCTION_FILE, Version::V7, be, le);
+        cross_check_sweep(0x7, 0x10000, be, le);
+    }
+
+    #[test]
+    fn arm8() {
+        let be = Decoder {
+            parse_arm: arm8_be::parse_arm,
+            parse_thumb: Some(arm8_be::parse_thumb),
+        };
+        let le = Decoder {
+            parse_arm: arm8_le::parse_arm,
+            parse_thumb: Some(arm8_le::parse_thumb),
+        };
+        cross_check_from_file(BASIS_INSTRUCTION_FILE, Version::V8, be, le);
+        cross_check_sweep(0x8, 0x10000, be, le);
+    }
+
+    #[test]
+    #[ignore]
+    fn arm8_sweep_big() {
+        let be = Decoder {
+            parse_arm: arm8_be::parse_arm,
+            parse_thumb: Some(arm8_be::parse_thumb),
+        };
+        let le = Decoder {
+            parse_arm: arm8_le::parse_arm,
+            parse_thumb: Some(arm8_le::parse_thumb),
+        };
+        cross_check_sweep(0x8b16, 0x100_0000, be, le);
+    }
+}
@@ -1,6 +1,7 @@
 use core::ops::RangeInclusive;
 use std::io::Read;
 
+use common_lib::XorShift;
 use serde::Deserialize;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
@@ -127,9 +128,11 @@ pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Tes
 //    sleigh_compile::from_path(&file_in).unwrap()
 //}
 
+pub type ParseFn = fn(&[u8], u32) -> Option<(u32, String)>;
+
 struct ParseStatic {
-    parse_arm: fn(&[u8], u32) -> Option<(u32, String)>,
-    parse_thumb: Option<fn(&[u8], u32) -> Option<(u32, String)>>,
+    parse_arm: ParseFn,
+    parse_thumb: Option<ParseFn>,
 }
 
 trait Parse {
@@ -216,8 +219,8 @@ pub fn tests_instruction_from_file(
     file: &str,
     version: Version,
     big_endian: bool,
-    parse_arm: fn(&[u8], u32) -> Option<(u32, String)>,
-    parse_thumb: Option<fn(&[u8], u32) -> Option<(u32, String)>>,
+    parse_arm: ParseFn,
+    parse_thumb: Option<ParseFn>,
 ) {
     let test_file = std::fs::File::open(file).unwrap();
     let tests = tests_from_file(test_file);
@@ -238,3 +241,93 @@ pub fn tests_instruction_from_file(
         //test_instruction(&test, version, big_endian, &mut parse_icicle);
     }
 }
+
+/// The parse functions of one generated crate, used to compare the big and
+/// little endian crates of the same version.
+#[derive(Clone, Copy)]
+pub struct Decoder {
+    pub parse_arm: ParseFn,
+    pub parse_thumb: Option<ParseFn>,
+}
+
+impl Decoder {
+    fn parse(
+        &self,
+        instruction: &Instruction,
+        big_endian: bool,
+        addr: u32,
+    ) -> Option<(u32, String)> {
+        let token = instruction.to_tokens(big_endian);
+        match instruction {
+            Instruction::Arm(_) => (self.parse_arm)(&token, addr),
+            Instruction::Thumb32(..) | Instruction::Thumb16(_) => {
+                self.parse_thumb.unwrap()(&token, addr)
+            }
+        }
+    }
+}
+
+/// Feed the same instruction to both crates, each with its own byte order, and
+/// check that both produce the same disassembly and length, returns if it was
+/// decoded, `None` if the crates have no Thumb mode.
+fn cross_check_instruction(
+    instruction: &Instruction,
+    addr: u32,
+    be: &Decoder,
+    le: &Decoder,
+) -> Option<bool> {
+    if instruction.thumb_mode() && (be.parse_thumb.is_none() || le.parse_thumb.is_none()) {
+        return None;
+    }
+    let be_result = be.parse(instruction, true, addr);
+    let le_result = le.parse(instruction, false, addr);
+    assert_eq!(
+        be_result, le_result,
+        "big/little endian mismatch at instruction {:x?} addr {:#x}",
+        instruction, addr
+    );
+    Some(be_result.is_some())
+}
+
+pub fn cross_check_from_file(file: &str, version: Version, be: Decoder, le: Decoder) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let tests = tests_from_file(test_file);
+    for test in tests.map(Result::unwrap) {
+        if !test.versions.contains(&version) {
+            continue;
+        }
+        let decoded = cross_check_instruction(&test.instruction, test.addr, &be, &le);
+        assert_ne!(
+            decoded,
+            Some(false),
+            "Unable to parse the {:x?} with expected output `{}`",
+            test.instruction,
+            test.result,
+        );
+    }
+}
+
+pub const CROSS_CHECK_ADDR: u32 = 0x1000;
+/// Check `arm_count` random ARM encodings and every Thumb first halfword, the
+/// ones that start a Thumb32 instruction get a random second halfword.
+pub fn cross_check_sweep(seed: u64, arm_count: usize, be: Decoder, le: Decoder) {
+    let mut rng = XorShift::new(seed);
+    let mut decoded = 0;
+    for _ in 0..arm_count {
+        let instruction = Instruction::Arm(rng.next_u32());
+        if cross_check_instruction(&instruction, CROSS_CHECK_ADDR, &be, &le) == Some(true) {
+            decoded += 1;
+        }
+    }
+    for first in 0..=u16::MAX {
+        let instruction = if first >> 11 >= 0b11101 {
+            Instruction::Thumb32(first, rng.next_u32() as u16)
+        } else {
+            Instruction::Thumb16(first)
+        };
+        if cross_check_instruction(&instruction, CROSS_CHECK_ADDR, &be, &le) == Some(true) {
+            decoded += 1;
+        }
+    }
+    assert_ne!(decoded, 0, "no instruction was decoded by the sweep");
+}
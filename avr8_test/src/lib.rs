@@ -0,0 +1,16 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use avr8::*;
+    use avr_lib::*;
+
+    #[test]
+    fn basic_instructions() {
+        tests_instruction_from_file(
+            BASIC_INSTRUCTION_FILE,
+            Core::Avr8,
+            AddressUnit::Word,
+            parse_default,
+        )
+    }
+}
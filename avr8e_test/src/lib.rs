@@ -0,0 +1,16 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use avr8e::*;
+    use avr_lib::*;
+
+    #[test]
+    fn basic_instructions() {
+        tests_instruction_from_file(
+            BASIC_INSTRUCTION_FILE,
+            Core::Avr8e,
+            AddressUnit::Word,
+            parse_default,
+        )
+    }
+}
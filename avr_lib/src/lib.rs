@@ -0,0 +1,99 @@
+use std::io::Read;
+
+use common_lib::{hex_units, test_token_or_undefined};
+use serde::Deserialize;
+
+/// The core revision, each one is a superset of the previous.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+pub enum Core {
+    #[serde(rename = "avr8")]
+    Avr8,
+    /// The enhanced core, with `MUL`, `MOVW`, `ELPM` and `EIJMP`, the avr8
+    /// crates can't decode them
+    #[serde(rename = "avr8e")]
+    Avr8e,
+}
+
+/// The size of the address unit of the crate code space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressUnit {
+    Byte,
+    /// The program memory is addressed by 16 bits words
+    Word,
+}
+impl AddressUnit {
+    pub fn bytes(&self) -> u32 {
+        match self {
+            AddressUnit::Byte => 1,
+            AddressUnit::Word => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Test {
+    pub core: Core,
+    /// Byte address in the flash
+    pub addr: u32,
+    /// One or two 16 bits words, `CALL`, `JMP`, `LDS` and `STS` use two
+    pub words: Vec<u16>,
+    pub result: String,
+}
+impl Test {
+    pub fn to_tokens(&self) -> Vec<u8> {
+        self.words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect()
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct TestSerialized {
+    core: Core,
+    addr: u32,
+    token: String,
+    result: String,
+}
+impl From<TestSerialized> for Test {
+    fn from(value: TestSerialized) -> Self {
+        Self {
+            core: value.core,
+            addr: value.addr,
+            words: hex_units(&value.token, 4),
+            result: value.result,
+        }
+    }
+}
+
+pub const BASIC_INSTRUCTION_FILE: &str = "../assets/avr/basic.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Test>> {
+    common_lib::tests_from_file::<TestSerialized, _, _>(file)
+}
+
+pub type ParseFn = common_lib::ParseFn<u32>;
+
+pub fn tests_instruction_from_file(
+    file: &str,
+    core: Core,
+    address_unit: AddressUnit,
+    parse: ParseFn,
+) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let tests = tests_from_file(test_file);
+    for test in tests.map(Result::unwrap) {
+        let token = test.to_tokens();
+        assert_eq!(test.addr % address_unit.bytes(), 0, "unaligned {:x?}", test);
+        let addr = test.addr / address_unit.bytes();
+        // the opcodes of a newer core are undefined on the older ones
+        let result = (test.core <= core).then_some(test.result.as_str());
+        let Some(next_addr) = test_token_or_undefined(&token, addr, result, parse) else {
+            continue;
+        };
+        assert_eq!(
+            next_addr,
+            addr + token.len() as u32 / address_unit.bytes(),
+            "at instruction {:x?}",
+            token
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! Helpers shared by the loaders.
+use std::io::Read;
+
+use serde::de::DeserializeOwned;
+
+/// xorshift64, good enough to spread the sweeps over the encoding space while
+/// keeping them reproducible from the seed.
+pub struct XorShift(u64);
+impl XorShift {
+    pub fn new(seed: u64) -> Self {
+        // zero is a fixed point
+        Self(seed | 1)
+    }
+    pub fn next_u32(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 32) as u32
+    }
+}
+
+/// Split the `hex` string in units of `digits` hex digits, eg `"0a0b"` with
+/// 2 digits is `[0x0a, 0x0b]`.
+pub fn hex_units<T>(hex: &str, digits: usize) -> Vec<T>
+where
+    T: TryFrom<u32>,
+    <T as TryFrom<u32>>::Error: core::fmt::Debug,
+{
+    hex.as_bytes()
+        .chunks(digits)
+        .map(|unit| u32::from_str_radix(std::str::from_utf8(unit).unwrap(), 16).unwrap())
+        .map(|unit| T::try_from(unit).unwrap())
+        .collect()
+}
+
+/// Deserialize the rows of the csv `file` as `S`, converted into `T`.
+pub fn tests_from_file<S, T, R>(file: R) -> impl Iterator<Item = csv::Result<T>>
+where
+    S: DeserializeOwned,
+    T: From<S>,
+    R: Read,
+{
+    csv::Reader::from_reader(file)
+        .into_deserialize::<S>()
+        .map(|test| test.map(T::from))
+}
+
+pub type ParseFn<A> = fn(&[u8], A) -> Option<(A, String)>;
+
+/// Decode the `token`, it need to produce the `result`, returns the next
+/// instruction address.
+pub fn test_token<A>(token: &[u8], addr: A, result: &str, parse: ParseFn<A>) -> A {
+    let Some((next_addr, output)) = parse(token, addr) else {
+        panic!(
+            "Unable to parse the {:x?} with expected output `{}`",
+            token, result,
+        );
+    };
+    assert_eq!(output, result, "at instruction {:x?}", token);
+    next_addr
+}
+
+/// Same as [test_token], but without a `result` the opcode is undefined, and
+/// the token can't be decoded.
+pub fn test_token_or_undefined<A>(
+    token: &[u8],
+    addr: A,
+    result: Option<&str>,
+    parse: ParseFn<A>,
+) -> Option<A> {
+    let Some(result) = result else {
+        assert!(
+            parse(token, addr).is_none(),
+            "undefined opcode {:x?} was decoded",
+            token
+        );
+        return None;
+    };
+    Some(test_token(token, addr, result, parse))
+}
@@ -0,0 +1,171 @@
+//! Corpus mnemonics, the mnemonics defined by the root table constructors of
+//! a SLEIGH spec that appear in the result column of the corpora.
+//!
+//! The decoder is not executed, a row counts for its mnemonic even if the
+//! test of that row fails, or if the row is only tested on some variants, so
+//! this is not a coverage of the decoder. Only exact mnemonics are matched,
+//! a display that appends to the mnemonic, eg: the `addeq` condition codes,
+//! is reported as not found in the spec.
+//!
+//! `cargo run -p coverage -- $GHIDRA_SRC/Ghidra/Processors/ARM/data/languages/ARM8_le.slaspec assets/arm/basic.csv`
+//!
+//! The spec preprocessor conditionals are ignored, so the spec mnemonics are
+//! the union of all the variants that share the `.sinc` files.
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Mnemonics of all the `:mnemonic ... is ...` constructors, following the
+/// `@include` directives relative to the including file.
+fn spec_mnemonics(file: &Path, mnemonics: &mut BTreeSet<String>) {
+    let spec = std::fs::read_to_string(file)
+        .unwrap_or_else(|err| panic!("Unable to read {}: {}", file.display(), err));
+    for line in spec.lines() {
+        let line = line.trim_start();
+        if let Some(include) = line.strip_prefix("@include") {
+            let include = include.trim().trim_matches('"');
+            spec_mnemonics(&file.parent().unwrap().join(include), mnemonics);
+            continue;
+        }
+        let Some(display) = line.strip_prefix(':') else {
+            continue;
+        };
+        // `:^instruction` are prefixes, not mnemonics
+        let mnemonic: String = display
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_'))
+            .collect();
+        if mnemonic.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            mnemonics.insert(mnemonic.to_lowercase());
+        }
+    }
+}
+
+/// Find the spec mnemonic for the disassembled mnemonic.
+fn match_mnemonic<'a>(mnemonic: &str, spec: &'a BTreeSet<String>) -> Option<&'a str> {
+    spec.get(mnemonic).map(String::as_str)
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let (Some(spec_file), corpus_files) = (args.next(), args.collect::<Vec<_>>()) else {
+        eprintln!("usage: coverage <file.slaspec> <corpus.csv>...");
+        std::process::exit(1);
+    };
+
+    let mut spec = BTreeSet::new();
+    spec_mnemonics(Path::new(&spec_file), &mut spec);
+
+    let mut in_corpus: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut unknown: BTreeMap<String, usize> = BTreeMap::new();
+    for corpus_file in corpus_files.iter() {
+        let mut reader = csv::Reader::from_path(corpus_file).unwrap();
+        let result_column = reader
+            .headers()
+            .unwrap()
+            .iter()
+            .position(|header| header == "result")
+            .unwrap_or_else(|| panic!("{} have no result column", corpus_file));
+        for record in reader.records().map(Result::unwrap) {
+            let result = &record[result_column];
+            // undefined opcodes
+            if result.is_empty() {
+                continue;
+            }
+            let mnemonic = result.split_whitespace().next().unwrap_or("");
+            let mnemonic = mnemonic.to_lowercase();
+            match match_mnemonic(&mnemonic, &spec) {
+                Some(mnemonic) => *in_corpus.entry(mnemonic).or_default() += 1,
+                None => *unknown.entry(mnemonic).or_default() += 1,
+            }
+        }
+    }
+
+    println!("| mnemonic | corpus rows |");
+    println!("|----------|-------------|");
+    for (mnemonic, rows) in in_corpus.iter() {
+        println!("| {} | {} |", mnemonic, rows);
+    }
+    println!();
+    let missing: Vec<_> = spec
+        .iter()
+        .filter(|mnemonic| !in_corpus.contains_key(mnemonic.as_str()))
+        .collect();
+    println!(
+        "not in the corpus: {} of {} mnemonics ({:.1}% in the corpus)",
+        missing.len(),
+        spec.len(),
+        100.0 * in_corpus.len() as f64 / spec.len().max(1) as f64,
+    );
+    println!();
+    println!("| mnemonic not in the corpus |");
+    println!("|----------------------------|");
+    for mnemonic in missing {
+        println!("| {} |", mnemonic);
+    }
+    if !unknown.is_empty() {
+        println!();
+        println!("not found in the spec:");
+        for (mnemonic, rows) in unknown.iter() {
+            println!("  {} ({} rows)", mnemonic, rows);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn spec(mnemonics: &[&str]) -> BTreeSet<String> {
+        mnemonics
+            .iter()
+            .map(|mnemonic| mnemonic.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn match_exact() {
+        let spec = spec(&["add", "adds", "b", "bl"]);
+        assert_eq!(match_mnemonic("add", &spec), Some("add"));
+        assert_eq!(match_mnemonic("bl", &spec), Some("bl"));
+    }
+
+    #[test]
+    fn match_no_prefix() {
+        let spec = spec(&["add", "b", "bl"]);
+        assert_eq!(match_mnemonic("addeq", &spec), None);
+        assert_eq!(match_mnemonic("bkpt", &spec), None);
+        assert_eq!(match_mnemonic("blx", &spec), None);
+    }
+
+    #[test]
+    fn match_unknown() {
+        let spec = spec(&["add", "b"]);
+        assert_eq!(match_mnemonic("sub", &spec), None);
+        assert_eq!(match_mnemonic("", &spec), None);
+    }
+
+    #[test]
+    fn spec_includes() {
+        let dir = std::env::temp_dir().join(format!("coverage_test_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(
+            dir.join("test.slaspec"),
+            "define endian=little;\n\
+            @include \"sub/test.sinc\"\n\
+            :NOP is op=0 {}\n\
+            :^instruction is prefix=1 & instruction {}\n\
+            :ld.w r, imm is op=1 & r & imm {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("sub/test.sinc"),
+            "  :ADD_S r is op=2 & r {}\n\
+            cc: \"eq\" is cond=0 {}\n",
+        )
+        .unwrap();
+        let mut mnemonics = BTreeSet::new();
+        spec_mnemonics(&dir.join("test.slaspec"), &mut mnemonics);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(mnemonics, spec(&["add_s", "ld.w", "nop"]));
+    }
+}
@@ -0,0 +1,16 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use cp1600::*;
+    use token_lib::*;
+
+    #[test]
+    fn basic_instructions() {
+        tests_instruction_from_file(CP1600_INSTRUCTION_FILE, 2, parse_default)
+    }
+    /// 10 bits decles in 16 bits words, one to three words long
+    #[test]
+    fn decle_lengths() {
+        check_lengths(0..=0x3ff, true, 2, &[1, 2, 3], parse_default)
+    }
+}
@@ -0,0 +1,11 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use cr16b::*;
+    use token_lib::*;
+
+    #[test]
+    fn lengths() {
+        check_lengths(0..=u16::MAX, false, 1, &[2, 4], parse_default)
+    }
+}
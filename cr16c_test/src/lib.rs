@@ -0,0 +1,15 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use cr16c::*;
+    use token_lib::*;
+
+    #[test]
+    fn basic_instructions() {
+        tests_instruction_from_file(CR16C_INSTRUCTION_FILE, 1, parse_default)
+    }
+    #[test]
+    fn lengths() {
+        check_lengths(0..=u16::MAX, false, 1, &[2, 4, 6], parse_default)
+    }
+}
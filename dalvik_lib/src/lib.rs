@@ -0,0 +1,132 @@
+use std::io::Read;
+
+use common_lib::{hex_units, test_token_or_undefined};
+use serde::Deserialize;
+
+/// The dex versions are ordered by Android release, the odex only match
+/// explicitly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Version {
+    Base,
+    KitKat,
+    Lollipop,
+    Marshmallow,
+    Nougat,
+    Oreo,
+    Pie,
+    Android10,
+    Android11,
+    Android12,
+    OdexKitKat,
+}
+impl Version {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "base" => Version::Base,
+            "kitkat" => Version::KitKat,
+            "lollipop" => Version::Lollipop,
+            "marshmallow" => Version::Marshmallow,
+            "nougat" => Version::Nougat,
+            "oreo" => Version::Oreo,
+            "pie" => Version::Pie,
+            "android10" => Version::Android10,
+            "android11" => Version::Android11,
+            "android12" => Version::Android12,
+            "odex_kitkat" => Version::OdexKitKat,
+            _ => panic!("Invalid Dalvik version `{}`", name),
+        }
+    }
+}
+
+/// A version, or with the `+` suffix, this and all the later dex versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionTag {
+    Only(Version),
+    Since(Version),
+}
+impl VersionTag {
+    fn from_name(name: &str) -> Self {
+        match name.strip_suffix('+') {
+            Some(since) => VersionTag::Since(Version::from_name(since)),
+            None => VersionTag::Only(Version::from_name(name)),
+        }
+    }
+    pub fn matches(&self, version: Version) -> bool {
+        match *self {
+            VersionTag::Only(only) => only == version,
+            VersionTag::Since(since) => version != Version::OdexKitKat && since <= version,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    /// Valid on all versions if empty
+    pub versions: Vec<VersionTag>,
+    pub addr: u32,
+    /// 1 to 5 16 bits code units
+    pub units: Vec<u16>,
+    /// `None` if the opcode is undefined in this versions
+    pub result: Option<String>,
+}
+impl Instruction {
+    pub fn to_tokens(&self) -> Vec<u8> {
+        self.units
+            .iter()
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect()
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct InstructionSerialized {
+    versions: String,
+    addr: u32,
+    token: String,
+    result: Option<String>,
+}
+impl From<InstructionSerialized> for Instruction {
+    fn from(value: InstructionSerialized) -> Self {
+        Self {
+            versions: value
+                .versions
+                .split_whitespace()
+                .map(VersionTag::from_name)
+                .collect(),
+            addr: value.addr,
+            units: hex_units(&value.token, 4),
+            result: value.result,
+        }
+    }
+}
+
+pub const BASIC_INSTRUCTION_FILE: &str = "../assets/dalvik/basic.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
+    common_lib::tests_from_file::<InstructionSerialized, _, _>(file)
+}
+
+pub type ParseFn = common_lib::ParseFn<u32>;
+
+pub fn tests_instruction_from_file(file: &str, version: Version, parse: ParseFn) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        if !instruction.versions.is_empty()
+            && !instruction.versions.iter().any(|tag| tag.matches(version))
+        {
+            continue;
+        }
+        let token = instruction.to_tokens();
+        let addr = instruction.addr;
+        let Some(next_addr) =
+            test_token_or_undefined(&token, addr, instruction.result.as_deref(), parse)
+        else {
+            continue;
+        };
+        assert_eq!(
+            next_addr,
+            addr + token.len() as u32,
+            "at instruction {:x?}",
+            token
+        );
+    }
+}
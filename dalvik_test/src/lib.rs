@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod test {
+    use dalvik_lib::*;
+
+    /// One test for each crate, all with the same corpus, `crate: version`.
+    macro_rules! dalvik_tests {
+        ($($name:ident: $version:expr),* $(,)?) => {
+            $(
+                #[test]
+                fn $name() {
+                    tests_instruction_from_file(BASIC_INSTRUCTION_FILE, $version, $name::parse_default);
+                }
+            )*
+        };
+    }
+
+    dalvik_tests! {
+        dalvik_base: Version::Base,
+        dalvik_dex_kitkat: Version::KitKat,
+        dalvik_dex_lollipop: Version::Lollipop,
+        dalvik_dex_marshmallow: Version::Marshmallow,
+        dalvik_dex_nougat: Version::Nougat,
+        dalvik_dex_oreo: Version::Oreo,
+        dalvik_dex_pie: Version::Pie,
+        dalvik_dex_android10: Version::Android10,
+        dalvik_dex_android11: Version::Android11,
+        dalvik_dex_android12: Version::Android12,
+        dalvik_odex_kitkat: Version::OdexKitKat,
+    }
+}
@@ -0,0 +1,11 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use dspic30f::*;
+    use pic24_lib::*;
+
+    #[test]
+    fn basic_instructions() {
+        tests_instruction_from_file(BASIC_INSTRUCTION_FILE, Family::Dspic, parse_default)
+    }
+}
@@ -0,0 +1,181 @@
+// Disassemble the tokens of a corpus with Ghidra, writing the `result` column.
+//
+// The input is a CSV with the `addr` and `token` (hex bytes in memory order)
+// columns, and optionally a `modes` column, with the space separated modes
+// that the row applies to. The arguments are the input and output files,
+// followed by the language id, or by one `<mode>=<language id>` for each mode.
+// A row must produce the same disassembly in all its modes, undefined opcodes
+// produce an empty result.
+//
+//   analyzeHeadless /tmp corpus -scriptPath ghidra -preScript DisassembleCorpus.java \
+//       tokens.csv result.csv x86:LE:32:default
+//
+//@category sleigh3test
+import java.io.ByteArrayInputStream;
+import java.io.IOException;
+import java.nio.charset.StandardCharsets;
+import java.nio.file.Files;
+import java.nio.file.Paths;
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+
+import ghidra.app.cmd.disassemble.DisassembleCommand;
+import ghidra.app.script.GhidraScript;
+import ghidra.program.database.ProgramDB;
+import ghidra.program.model.address.Address;
+import ghidra.program.model.lang.Language;
+import ghidra.program.model.lang.LanguageID;
+import ghidra.program.model.listing.Instruction;
+import ghidra.program.model.listing.Program;
+import ghidra.program.util.DefaultLanguageService;
+
+public class DisassembleCorpus extends GhidraScript {
+
+	@Override
+	protected void run() throws Exception {
+		String[] args = getScriptArgs();
+		if (args.length < 3) {
+			throw new IllegalArgumentException(
+				"usage: <input.csv> <output.csv> <language id> | <mode>=<language id>...");
+		}
+		Map<String, Language> languages = new HashMap<>();
+		for (int i = 2; i < args.length; i++) {
+			int split = args[i].indexOf('=');
+			String mode = split < 0 ? "" : args[i].substring(0, split);
+			LanguageID id = new LanguageID(args[i].substring(split + 1));
+			languages.put(mode, DefaultLanguageService.getLanguageService().getLanguage(id));
+		}
+
+		List<String> lines = Files.readAllLines(Paths.get(args[0]), StandardCharsets.UTF_8);
+		List<String> header = parseRow(lines.get(0));
+		int addrColumn = header.indexOf("addr");
+		int tokenColumn = header.indexOf("token");
+		int modesColumn = header.indexOf("modes");
+		int resultColumn = header.indexOf("result");
+		if (resultColumn < 0) {
+			header.add("result");
+			resultColumn = header.size() - 1;
+		}
+
+		StringBuilder output = new StringBuilder(formatRow(header));
+		for (String line : lines.subList(1, lines.size())) {
+			if (line.isEmpty()) {
+				continue;
+			}
+			List<String> row = parseRow(line);
+			long addr = Long.parseLong(row.get(addrColumn));
+			byte[] token = parseHex(row.get(tokenColumn));
+			String[] modes = modesColumn < 0 ? new String[] { "" } : row.get(modesColumn).split(" ");
+			String result = null;
+			for (String mode : modes) {
+				Language language = languages.get(mode);
+				if (language == null) {
+					throw new IllegalArgumentException("no language for the mode `" + mode + "`");
+				}
+				String modeResult = disassemble(language, addr, token);
+				if (result != null && !result.equals(modeResult)) {
+					throw new IllegalStateException("the row `" + line +
+						"` disassembles to `" + result + "` and `" + modeResult + "`");
+				}
+				result = modeResult;
+			}
+			while (row.size() <= resultColumn) {
+				row.add("");
+			}
+			row.set(resultColumn, result);
+			output.append(formatRow(row));
+		}
+		Files.write(Paths.get(args[1]), output.toString().getBytes(StandardCharsets.UTF_8));
+	}
+
+	/** The instruction at `addr`, it must use the whole token. */
+	private String disassemble(Language language, long addr, byte[] token) throws Exception {
+		Program program =
+			new ProgramDB("corpus", language, language.getDefaultCompilerSpec(), this);
+		try {
+			int transaction = program.startTransaction("disassemble");
+			Address start = language.getDefaultSpace().getAddress(addr);
+			program.getMemory()
+					.createInitializedBlock("token", start, new ByteArrayInputStream(token),
+						token.length, monitor, false);
+			new DisassembleCommand(start, null, false).applyTo(program, monitor);
+			Instruction instruction = program.getListing().getInstructionAt(start);
+			program.endTransaction(transaction, false);
+			if (instruction == null) {
+				return "";
+			}
+			if (instruction.getLength() != token.length) {
+				throw new IllegalStateException("the token at " + start + " has " +
+					token.length + " bytes but the instruction `" + instruction + "` has " +
+					instruction.getLength());
+			}
+			return instruction.toString();
+		}
+		finally {
+			program.release(this);
+		}
+	}
+
+	private static byte[] parseHex(String hex) {
+		byte[] bytes = new byte[hex.length() / 2];
+		for (int i = 0; i < bytes.length; i++) {
+			bytes[i] = (byte) Integer.parseInt(hex.substring(i * 2, i * 2 + 2), 16);
+		}
+		return bytes;
+	}
+
+	private static List<String> parseRow(String line) throws IOException {
+		List<String> fields = new ArrayList<>();
+		StringBuilder field = new StringBuilder();
+		boolean quoted = false;
+		for (int i = 0; i < line.length(); i++) {
+			char c = line.charAt(i);
+			if (quoted) {
+				if (c == '"' && i + 1 < line.length() && line.charAt(i + 1) == '"') {
+					field.append('"');
+					i++;
+				}
+				else if (c == '"') {
+					quoted = false;
+				}
+				else {
+					field.append(c);
+				}
+			}
+			else if (c == '"') {
+				quoted = true;
+			}
+			else if (c == ',') {
+				fields.add(field.toString());
+				field.setLength(0);
+			}
+			else {
+				field.append(c);
+			}
+		}
+		if (quoted) {
+			throw new IOException("unterminated quote in `" + line + "`");
+		}
+		fields.add(field.toString());
+		return fields;
+	}
+
+	/** Quote only the fields that need it, like the csv crate. */
+	private static String formatRow(List<String> fields) {
+		StringBuilder line = new StringBuilder();
+		for (String field : fields) {
+			if (line.length() > 0) {
+				line.append(',');
+			}
+			if (field.contains(",") || field.contains("\"") || field.contains("\n")) {
+				line.append('"').append(field.replace("\"", "\"\"")).append('"');
+			}
+			else {
+				line.append(field);
+			}
+		}
+		return line.append('\n').toString();
+	}
+}
@@ -0,0 +1,11 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use h6309::*;
+    use m68xx_lib::*;
+
+    #[test]
+    fn basic_instructions() {
+        tests_instruction_from_file(BASIC_INSTRUCTION_FILE, Cpu::H6309, parse_default)
+    }
+}
@@ -0,0 +1,49 @@
+use std::io::Read;
+
+use common_lib::{hex_units, test_token_or_undefined};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Instruction {
+    /// Offset from the method start, the switches padding depends on it
+    pub addr: u32,
+    token: String,
+    /// `None` if the opcode is undefined
+    pub result: Option<String>,
+}
+impl Instruction {
+    pub fn to_tokens(&self) -> Vec<u8> {
+        hex_units(&self.token, 2)
+    }
+}
+
+/// Every opcode, the constant pool indexes are not resolved, followed by
+/// the `wide` forms and the `tableswitch`/`lookupswitch` at each alignment.
+pub const OPCODE_INSTRUCTION_FILE: &str = "../assets/jvm/opcode.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
+    common_lib::tests_from_file::<Instruction, _, _>(file)
+}
+
+pub type ParseFn = common_lib::ParseFn<u32>;
+
+pub fn tests_instruction_from_file(file: &str, parse: ParseFn) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        let token = instruction.to_tokens();
+        let addr = instruction.addr;
+        let Some(next_addr) =
+            test_token_or_undefined(&token, addr, instruction.result.as_deref(), parse)
+        else {
+            continue;
+        };
+        // the token is exactly one instruction, including the switch padding
+        assert_eq!(
+            next_addr,
+            addr + token.len() as u32,
+            "at instruction {:x?} addr {:#x}",
+            token,
+            addr
+        );
+    }
+}
@@ -0,0 +1,11 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use jvm::*;
+    use jvm_lib::*;
+
+    #[test]
+    fn opcode_instructions() {
+        tests_instruction_from_file(OPCODE_INSTRUCTION_FILE, parse_default)
+    }
+}
@@ -0,0 +1,90 @@
+use std::io::Read;
+
+use common_lib::{hex_units, test_token_or_undefined};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cpu {
+    M6805,
+    M6809,
+    /// The 6809 with the `0x10`/`0x11` page extensions
+    H6309,
+    Hc05,
+    /// The HC05 with the `0x9e` page
+    Hc08,
+    Hcs08,
+    /// The `0x18` page
+    Hcs12,
+}
+impl Cpu {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "6805" => Cpu::M6805,
+            "6809" => Cpu::M6809,
+            "h6309" => Cpu::H6309,
+            "hc05" => Cpu::Hc05,
+            "hc08" => Cpu::Hc08,
+            "hcs08" => Cpu::Hcs08,
+            "hcs12" => Cpu::Hcs12,
+            _ => panic!("Invalid 68xx cpu `{}`", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    /// The CPUs that share this row
+    pub cpu: Vec<Cpu>,
+    pub addr: u16,
+    /// The prefix, opcode, postbyte and operand bytes, big endian
+    pub token: Vec<u8>,
+    /// `None` if the opcode is undefined
+    pub result: Option<String>,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct InstructionSerialized {
+    cpu: String,
+    addr: u16,
+    token: String,
+    result: Option<String>,
+}
+impl From<InstructionSerialized> for Instruction {
+    fn from(value: InstructionSerialized) -> Self {
+        Self {
+            cpu: value.cpu.split_whitespace().map(Cpu::from_name).collect(),
+            addr: value.addr,
+            token: hex_units(&value.token, 2),
+            result: value.result,
+        }
+    }
+}
+
+pub const BASIC_INSTRUCTION_FILE: &str = "../assets/m68xx/basic.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
+    common_lib::tests_from_file::<InstructionSerialized, _, _>(file)
+}
+
+pub type ParseFn = common_lib::ParseFn<u16>;
+
+pub fn tests_instruction_from_file(file: &str, cpu: Cpu, parse: ParseFn) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        if !instruction.cpu.contains(&cpu) {
+            continue;
+        }
+        let token = &instruction.token;
+        let addr = instruction.addr;
+        let Some(next_addr) =
+            test_token_or_undefined(token, addr, instruction.result.as_deref(), parse)
+        else {
+            continue;
+        };
+        assert_eq!(
+            next_addr,
+            addr + token.len() as u16,
+            "at instruction {:x?}",
+            token
+        );
+    }
+}
@@ -0,0 +1,11 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use m8c::*;
+    use token_lib::*;
+
+    #[test]
+    fn basic_instructions() {
+        tests_instruction_from_file(M8C_INSTRUCTION_FILE, 1, parse_default)
+    }
+}
@@ -0,0 +1,577 @@
+//! Generate the test crates and the workspace members from `matrix.toml`.
+//!
+//! `cargo run --manifest-path matrix/Cargo.toml`
+//!
+//! With `--check` nothing is written, it fails if any generated file is out
+//! of date with the matrix.
+//!
+//! The generated decoder crates are found in `--sleigh3rust <dir>`, or the
+//! `SLEIGH3RUST` env var, or the matrix `workspace.sleigh3rust`, in this
+//! order. The dir is relative to the current dir, except the matrix one that
+//! is relative to the matrix. The generated manifests are tracked, so the
+//! dir is written relative to the matrix, and `--check` ignores the
+//! overrides, comparing with the matrix dir.
+//!
+//! With `--test <family>[,<family>...]` only the crates of the families are
+//! tested, so only their decoders are built, `all` tests the whole workspace.
+//!
+//! With `--slaspec <file.slaspec> <crate>` the decoder of the matrix crate is
+//! generated from the local spec, and the crate tests are executed with it,
+//! the arguments after `--` are passed to `cargo test`:
+//!
+//! `cargo run --manifest-path matrix/Cargo.toml -- --slaspec $GHIDRA_SRC/Ghidra/Processors/Z80/data/languages/z80.slaspec z80_test`
+mod slaspec;
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Matrix {
+    workspace: Workspace,
+    #[serde(default)]
+    suite: BTreeMap<String, Suite>,
+    #[serde(rename = "crate")]
+    crates: Vec<Crate>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Workspace {
+    /// Default dir of the generated decoder crates
+    sleigh3rust: PathBuf,
+    /// Members that are not test crates, like the loaders and tools
+    members: Vec<String>,
+}
+
+/// Tests shared by multiple crates, the `${var}` in the test bodies are
+/// replaced by the crate `vars`, so the Rust braces are left alone.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Suite {
+    loader: String,
+    family: Option<String>,
+    test: Vec<Test>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Test {
+    name: String,
+    body: String,
+    #[serde(default)]
+    ignore: bool,
+    doc: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Crate {
+    /// Defaults to `<decoder>_test`
+    name: Option<String>,
+    decoder: Option<String>,
+    /// Crates testing more than one decoder
+    #[serde(default)]
+    decoders: Vec<String>,
+    suite: Option<String>,
+    /// Only required if there is no suite
+    loader: Option<String>,
+    /// Only required if there is no suite, or the suite have no family
+    family: Option<String>,
+    /// Only this tests from the suite, instead of all of them
+    tests: Option<Vec<String>>,
+    /// Tests only used by this crate, after the suite ones
+    #[serde(default)]
+    test: Vec<Test>,
+    /// Suite tests named differently in this crate, `suite_name = "name"`
+    #[serde(default)]
+    test_names: BTreeMap<String, String>,
+    #[serde(default)]
+    vars: BTreeMap<String, String>,
+    /// Disabled crates are only listed, commented, in the workspace
+    #[serde(default = "enabled_default")]
+    enabled: bool,
+}
+
+fn enabled_default() -> bool {
+    true
+}
+
+impl Crate {
+    fn decoders(&self) -> Vec<&str> {
+        self.decoder
+            .iter()
+            .chain(self.decoders.iter())
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn name(&self) -> String {
+        match (&self.name, self.decoders().as_slice()) {
+            (Some(name), _) => name.clone(),
+            (None, [decoder]) => format!("{}_test", decoder),
+            (None, _) => panic!("crate with multiple decoders need a name"),
+        }
+    }
+
+    fn suite<'a>(&self, matrix: &'a Matrix) -> Option<&'a Suite> {
+        self.suite.as_ref().map(|suite| {
+            matrix
+                .suite
+                .get(suite)
+                .unwrap_or_else(|| panic!("{}: unknown suite `{}`", self.name(), suite))
+        })
+    }
+
+    fn loader<'a>(&'a self, matrix: &'a Matrix) -> &'a str {
+        match (self.suite(matrix), &self.loader) {
+            (_, Some(loader)) => loader,
+            (Some(suite), None) => &suite.loader,
+            (None, None) => panic!("{}: crate without suite or loader", self.name()),
+        }
+    }
+
+    fn family<'a>(&'a self, matrix: &'a Matrix) -> &'a str {
+        match (self.suite(matrix), &self.family) {
+            (_, Some(family)) => family,
+            (
+                Some(Suite {
+                    family: Some(family),
+                    ..
+                }),
+                None,
+            ) => family,
+            (_, None) => panic!("{}: crate without family", self.name()),
+        }
+    }
+
+    /// The suite tests followed by the crate ones, with the vars replaced.
+    fn tests(&self, matrix: &Matrix) -> Vec<Test> {
+        let mut tests: Vec<Test> = match (self.suite(matrix), &self.tests) {
+            (None, _) => vec![],
+            (Some(suite), None) => suite.test.clone(),
+            (Some(suite), Some(names)) => names
+                .iter()
+                .map(|name| {
+                    suite
+                        .test
+                        .iter()
+                        .find(|test| &test.name == name)
+                        .unwrap_or_else(|| panic!("{}: unknown test `{}`", self.name(), name))
+                        .clone()
+                })
+                .collect(),
+        };
+        for (suite_name, name) in &self.test_names {
+            let test = tests
+                .iter_mut()
+                .find(|test| &test.name == suite_name)
+                .unwrap_or_else(|| panic!("{}: unknown test `{}`", self.name(), suite_name));
+            test.name = name.clone();
+        }
+        tests.extend(self.test.iter().cloned());
+        for test in tests.iter_mut() {
+            test.body = self.replace_vars(&test.body);
+        }
+        tests
+    }
+
+    fn replace_vars(&self, body: &str) -> String {
+        let mut output = String::new();
+        let mut rest = body;
+        while let Some(start) = rest.find("${") {
+            output.push_str(&rest[..start]);
+            rest = &rest[start + 2..];
+            let var = rest
+                .find('}')
+                .map(|end| &rest[..end])
+                .filter(|var| {
+                    !var.is_empty() && var.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                })
+                .unwrap_or_else(|| panic!("{}: invalid `${{var}}` in `{}`", self.name(), body));
+            let value = self
+                .vars
+                .get(var)
+                .unwrap_or_else(|| panic!("{}: missing var `{}`", self.name(), var));
+            output.push_str(value);
+            rest = &rest[var.len() + 1..];
+        }
+        output.push_str(rest);
+        output
+    }
+}
+
+/// `sleigh3rust` is the dir of the decoders, relative to the test crate.
+fn cargo_toml(name: &str, sleigh3rust: &Path, decoders: &[&str], loader: &str) -> String {
+    let mut output = format!(
+        "# Generated from matrix.toml, do not edit.\n\
+        [package]\n\
+        name = \"{}\"\n\
+        version = \"0.1.0\"\n\
+        edition = \"2021\"\n\
+        \n\
+        [dependencies]\n",
+        name
+    );
+    for decoder in decoders {
+        output.push_str(&format!(
+            "{} = {{ path = \"{}\" }}\n",
+            decoder,
+            sleigh3rust.join(decoder).display()
+        ));
+    }
+    output.push_str(&format!("{} = {{ path = \"../{}\" }}\n", loader, loader));
+    output
+}
+
+fn lib_rs(decoder: Option<&str>, loader: &str, tests: &[Test]) -> String {
+    let mut uses: Vec<&str> = decoder.into_iter().chain([loader]).collect();
+    uses.sort();
+    let mut output = String::from("// Generated from matrix.toml, do not edit.\n");
+    output.push_str("#[cfg(test)]\nmod test {\n");
+    for name in uses {
+        output.push_str(&format!("use {}::*;\n", name));
+    }
+    output.push('\n');
+    for test in tests {
+        if let Some(doc) = &test.doc {
+            for line in doc.lines() {
+                output.push_str(&format!("/// {}\n", line));
+            }
+        }
+        output.push_str("#[test]\n");
+        if test.ignore {
+            output.push_str("#[ignore]\n");
+        }
+        output.push_str(&format!(
+            "fn {}() {{\n{}\n}}\n",
+            test.name,
+            test.body.trim()
+        ));
+    }
+    output.push_str("}\n");
+    rustfmt(&output)
+}
+
+fn rustfmt(source: &str) -> String {
+    let mut rustfmt = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Unable to execute rustfmt");
+    rustfmt
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(source.as_bytes())
+        .unwrap();
+    let output = rustfmt.wait_with_output().unwrap();
+    assert!(output.status.success(), "rustfmt failed:\n{}", source);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn workspace_toml(matrix: &Matrix) -> String {
+    let mut output = String::from(
+        "# Generated from matrix.toml by `cargo run --manifest-path matrix/Cargo.toml`,\n\
+        # do not edit.\n\
+        [workspace]\n\
+        \n\
+        members = [\n",
+    );
+    for member in matrix.workspace.members.iter() {
+        output.push_str(&format!("    \"{}\",\n", member));
+    }
+    let mut last_loader = None;
+    for krate in matrix.crates.iter() {
+        // group the crates by loader
+        let loader = krate.loader(matrix);
+        if last_loader != Some(loader) {
+            output.push('\n');
+            last_loader = Some(loader);
+        }
+        let comment = if krate.enabled { "" } else { "#" };
+        output.push_str(&format!("    {}\"{}\",\n", comment, krate.name()));
+    }
+    output.push_str("]\n");
+    output
+}
+
+/// The enabled crates of each family.
+fn families_of(matrix: &Matrix) -> BTreeMap<&str, Vec<String>> {
+    let mut families: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for krate in matrix.crates.iter().filter(|krate| krate.enabled) {
+        families
+            .entry(krate.family(matrix))
+            .or_default()
+            .push(krate.name());
+    }
+    families
+}
+
+/// `cargo test-<family>` aliases, that only build the family decoders.
+fn cargo_config(matrix: &Matrix) -> String {
+    let mut output = String::from(
+        "# Generated from matrix.toml, do not edit.\n\
+        [alias]\n",
+    );
+    for (family, crates) in families_of(matrix) {
+        output.push_str(&format!("test-{} = [\"test\"", family));
+        for name in crates {
+            output.push_str(&format!(", \"-p\", \"{}\"", name));
+        }
+        output.push_str("]\n");
+    }
+    output
+}
+
+/// Execute the tests of the crates of the comma separated `families`, or all
+/// the workspace with `all`.
+fn test_families(root: &Path, matrix: &Matrix, families: &str, cargo_args: &[String]) -> ! {
+    let all = families_of(matrix);
+    let mut cargo = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cargo.arg("test").current_dir(root);
+    if families == "all" {
+        cargo.arg("--workspace");
+    } else {
+        for family in families.split(',') {
+            let Some(crates) = all.get(family) else {
+                eprintln!(
+                    "Unknown family `{}`, available: all, {}",
+                    family,
+                    all.keys().copied().collect::<Vec<_>>().join(", ")
+                );
+                std::process::exit(1);
+            };
+            for name in crates {
+                cargo.args(["-p", name]);
+            }
+        }
+    }
+    let status = cargo
+        .args(cargo_args)
+        .status()
+        .expect("Unable to execute cargo");
+    std::process::exit(status.code().unwrap_or(1));
+}
+
+/// The generated files, path and content.
+fn generate(root: &Path, sleigh3rust: &Path, matrix: &Matrix) -> Vec<(PathBuf, String)> {
+    let mut files = vec![
+        (root.join("Cargo.toml"), workspace_toml(matrix)),
+        (root.join(".cargo/config.toml"), cargo_config(matrix)),
+    ];
+    for krate in matrix.crates.iter().filter(|krate| krate.enabled) {
+        let name = krate.name();
+        let dir = root.join(&name);
+        let decoders = krate.decoders();
+        let loader = krate.loader(matrix);
+        files.push((
+            dir.join("Cargo.toml"),
+            cargo_toml(&name, sleigh3rust, &decoders, loader),
+        ));
+        files.push((dir.join(".gitignore"), "/target\n/Cargo.lock\n".to_string()));
+        // crates without tests have a hand written lib.rs
+        let tests = krate.tests(matrix);
+        if !tests.is_empty() {
+            let decoder = match decoders.as_slice() {
+                [decoder] => Some(*decoder),
+                _ => None,
+            };
+            files.push((dir.join("src/lib.rs"), lib_rs(decoder, loader, &tests)));
+        }
+    }
+    files
+}
+
+/// The `path` relative to the `base` dir, both resolved if they exist.
+fn relative_path(base: &Path, path: &Path) -> PathBuf {
+    let base = std::fs::canonicalize(base).unwrap_or_else(|_| base.to_path_buf());
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let common = base
+        .components()
+        .zip(path.components())
+        .take_while(|(base, path)| base == path)
+        .count();
+    let mut relative = PathBuf::new();
+    for _ in base.components().skip(common) {
+        relative.push("..");
+    }
+    relative.extend(path.components().skip(common));
+    relative
+}
+
+fn usage() -> ! {
+    eprintln!("usage: matrix [--check] [--sleigh3rust <dir>]");
+    eprintln!("       matrix --test <family>[,<family>...] [-- <cargo test args>...]");
+    eprintln!("       matrix [--sleigh3rust <dir>] --slaspec <file.slaspec> <crate> [-- <cargo test args>...]");
+    std::process::exit(1);
+}
+
+fn main() {
+    let mut check = false;
+    let mut sleigh3rust = std::env::var_os("SLEIGH3RUST").map(PathBuf::from);
+    let mut slaspec: Option<(PathBuf, String)> = None;
+    let mut test: Option<String> = None;
+    let mut cargo_args = vec![];
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--sleigh3rust" => sleigh3rust = Some(args.next().unwrap_or_else(|| usage()).into()),
+            "--slaspec" => {
+                let file = args.next().unwrap_or_else(|| usage());
+                let krate = args.next().unwrap_or_else(|| usage());
+                slaspec = Some((file.into(), krate));
+            }
+            "--test" => test = Some(args.next().unwrap_or_else(|| usage())),
+            "--" if slaspec.is_some() || test.is_some() => cargo_args.extend(args.by_ref()),
+            _ => usage(),
+        }
+    }
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let matrix_file = root.join("matrix.toml");
+    let matrix = std::fs::read_to_string(&matrix_file)
+        .unwrap_or_else(|err| panic!("Unable to read {}: {}", matrix_file.display(), err));
+    let matrix: Matrix = toml::from_str(&matrix).unwrap_or_else(|err| panic!("{}", err));
+    if let Some(test) = test {
+        test_families(root, &matrix, &test, &cargo_args);
+    }
+
+    // relative to the root
+    let sleigh3rust = match sleigh3rust {
+        Some(dir) if !check || slaspec.is_some() => {
+            relative_path(root, &std::env::current_dir().unwrap().join(dir))
+        }
+        _ => matrix.workspace.sleigh3rust.clone(),
+    };
+    if let Some((file, name)) = slaspec {
+        let krate = matrix
+            .crates
+            .iter()
+            .find(|krate| krate.name() == name)
+            .unwrap_or_else(|| panic!("Crate `{}` not found in the matrix", name));
+        slaspec::test_slaspec(root, &sleigh3rust, &matrix, krate, &file, &cargo_args);
+    }
+    let mut missing: Vec<_> = matrix
+        .crates
+        .iter()
+        .filter(|krate| krate.enabled)
+        .flat_map(|krate| krate.decoders())
+        .filter(|decoder| !root.join(&sleigh3rust).join(decoder).exists())
+        .collect();
+    missing.sort();
+    missing.dedup();
+    if !missing.is_empty() {
+        eprintln!(
+            "warning: {} decoders not found in {}: {}",
+            missing.len(),
+            sleigh3rust.display(),
+            missing.join(", ")
+        );
+    }
+    // the test crates are one dir inside the root
+    let sleigh3rust = Path::new("..").join(sleigh3rust);
+
+    let mut outdated = 0;
+    for (path, content) in generate(root, &sleigh3rust, &matrix) {
+        let current = std::fs::read_to_string(&path).ok();
+        if current.as_deref() == Some(content.as_str()) {
+            continue;
+        }
+        outdated += 1;
+        if check {
+            println!("outdated: {}", path.strip_prefix(root).unwrap().display());
+            continue;
+        }
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+    }
+    if check && outdated != 0 {
+        eprintln!("{} files out of date, run the matrix generator", outdated);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn krate(source: &str) -> Crate {
+        toml::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn replace_vars() {
+        let krate = krate(
+            r#"decoder = "z80"
+vars = { cpu = "Cpu::Z80", file = "FILE" }"#,
+        );
+        assert_eq!(
+            krate.replace_vars("tests(${file}, ${cpu}, parse_default)"),
+            "tests(FILE, Cpu::Z80, parse_default)"
+        );
+    }
+
+    #[test]
+    fn replace_vars_keep_braces() {
+        let krate = krate(r#"decoder = "z80""#);
+        let body = "let x = X { cpu: {cpu} }; format!(\"{x}\")";
+        assert_eq!(krate.replace_vars(body), body);
+    }
+
+    #[test]
+    #[should_panic(expected = "missing var `cpu`")]
+    fn replace_vars_missing() {
+        krate(r#"decoder = "z80""#).replace_vars("${cpu}");
+    }
+
+    #[test]
+    fn relative_paths() {
+        let base = Path::new("/nonexistent/sleigh3test");
+        assert_eq!(
+            relative_path(base, Path::new("/nonexistent/sleigh3rust")),
+            Path::new("../sleigh3rust")
+        );
+        assert_eq!(
+            relative_path(base, Path::new("/nonexistent/sleigh3test/build/out")),
+            Path::new("build/out")
+        );
+        assert_eq!(
+            relative_path(base, Path::new("/other/sleigh3rust")),
+            Path::new("../../other/sleigh3rust")
+        );
+    }
+
+    #[test]
+    fn test_names() {
+        let matrix: Matrix = toml::from_str(
+            r#"[workspace]
+sleigh3rust = "../sleigh3rust"
+members = []
+[suite.z80]
+loader = "z80_lib"
+[[suite.z80.test]]
+name = "basic"
+body = "basic()"
+[[suite.z80.test]]
+name = "random"
+body = "random()"
+[[crate]]
+decoder = "z80"
+suite = "z80"
+test_names = { basic = "mov" }"#,
+        )
+        .unwrap();
+        let names: Vec<_> = matrix.crates[0]
+            .tests(&matrix)
+            .into_iter()
+            .map(|test| test.name)
+            .collect();
+        assert_eq!(names, ["mov", "random"]);
+    }
+}
@@ -0,0 +1,241 @@
+//! Test a local `.slaspec` without publishing it into sleigh3rust.
+//!
+//! The decoder crate of a matrix crate is copied into `slaspec_test/decoder`,
+//! with the `.slaspec` path in its sources replaced, so sleigh2rust generate
+//! the decoder from the local spec. The matrix crate tests are then executed
+//! with it.
+//!
+//! This depends on the layout of the sleigh3rust crates, the spec is the
+//! `"<path>.slaspec"` string literal passed to sleigh2rust in the crate
+//! sources, eg: by the build script. A crate without it is reported, instead
+//! of testing the published decoder.
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{lib_rs, Crate, Matrix};
+
+/// Outside the workspace, but one dir inside the root, so the loaders find
+/// the corpus.
+const TEST_DIR: &str = "slaspec_test";
+
+/// Make the relative `path` dependencies absolute, they are relative to the
+/// decoder crate original location.
+fn rewrite_manifest(manifest: &str, decoder_dir: &Path) -> String {
+    let mut manifest: toml::Table = toml::from_str(manifest).unwrap();
+    for table in ["dependencies", "build-dependencies", "dev-dependencies"] {
+        let Some(deps) = manifest.get_mut(table).and_then(|deps| deps.as_table_mut()) else {
+            continue;
+        };
+        for (name, dep) in deps.iter_mut() {
+            let Some(dep) = dep.as_table_mut() else {
+                continue;
+            };
+            if dep.contains_key("workspace") {
+                panic!("dependency `{}` is inherited from the workspace", name);
+            }
+            if let Some(path) = dep.get_mut("path") {
+                let dir = decoder_dir.join(path.as_str().unwrap());
+                let dir = std::fs::canonicalize(&dir).unwrap_or(dir);
+                *path = dir.display().to_string().into();
+            }
+        }
+    }
+    toml::to_string(&manifest).unwrap()
+}
+
+/// Replace the `"*.slaspec"` string literals, returns the number replaced.
+fn rewrite_source(source: &mut String, slaspec: &Path) -> usize {
+    const SUFFIX: &str = ".slaspec\"";
+    let mut output = String::new();
+    let mut replaced = 0;
+    let mut rest = source.as_str();
+    while let Some(end) = rest.find(SUFFIX) {
+        // the literal starts in the same line
+        let line_start = rest[..end].rfind('\n').map_or(0, |start| start + 1);
+        let Some(start) = rest[line_start..end].rfind('"') else {
+            output.push_str(&rest[..end + SUFFIX.len()]);
+            rest = &rest[end + SUFFIX.len()..];
+            continue;
+        };
+        output.push_str(&rest[..line_start + start]);
+        output.push_str(&format!("{:?}", slaspec.display().to_string()));
+        rest = &rest[end + SUFFIX.len()..];
+        replaced += 1;
+    }
+    output.push_str(rest);
+    *source = output;
+    replaced
+}
+
+/// Copy the decoder crate, without the build output, rewriting the
+/// manifest and the `.slaspec` paths, returns the number of paths replaced.
+fn copy_decoder(from: &Path, to: &Path, slaspec: &Path) -> usize {
+    std::fs::create_dir_all(to).unwrap();
+    let mut replaced = 0;
+    for entry in std::fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let (from_file, to_file) = (entry.path(), to.join(entry.file_name()));
+        let file_name = entry.file_name();
+        match file_name.to_str() {
+            Some("target" | "Cargo.lock") => continue,
+            Some("Cargo.toml") => {
+                let manifest = std::fs::read_to_string(&from_file).unwrap();
+                let decoder_dir = from_file.parent().unwrap();
+                std::fs::write(to_file, rewrite_manifest(&manifest, decoder_dir)).unwrap();
+            }
+            _ if entry.file_type().unwrap().is_dir() => {
+                replaced += copy_decoder(&from_file, &to_file, slaspec)
+            }
+            Some(name) if name.ends_with(".rs") => {
+                let mut source = std::fs::read_to_string(&from_file).unwrap();
+                replaced += rewrite_source(&mut source, slaspec);
+                std::fs::write(to_file, source).unwrap();
+            }
+            _ => {
+                std::fs::copy(&from_file, &to_file).unwrap();
+            }
+        }
+    }
+    replaced
+}
+
+fn test_cargo_toml(decoder: &str, loader: &str) -> String {
+    format!(
+        "# Generated by the matrix tool from a local slaspec, do not edit.\n\
+        [package]\n\
+        name = \"{}\"\n\
+        version = \"0.1.0\"\n\
+        edition = \"2021\"\n\
+        \n\
+        [workspace]\n\
+        \n\
+        [dependencies]\n\
+        {} = {{ path = \"decoder\" }}\n\
+        {} = {{ path = \"../{}\" }}\n",
+        TEST_DIR, decoder, loader, loader
+    )
+}
+
+/// Generate the decoder of the `krate` from `slaspec` and execute the
+/// crate tests with it, `cargo_args` are passed to `cargo test`.
+pub fn test_slaspec(
+    root: &Path,
+    sleigh3rust: &Path,
+    matrix: &Matrix,
+    krate: &Crate,
+    slaspec: &Path,
+    cargo_args: &[String],
+) -> ! {
+    let slaspec: PathBuf = std::fs::canonicalize(slaspec)
+        .unwrap_or_else(|err| panic!("Unable to find {}: {}", slaspec.display(), err));
+    let [decoder] = krate.decoders()[..] else {
+        panic!(
+            "{}: only crates with one decoder can use a slaspec",
+            krate.name()
+        );
+    };
+    let tests = krate.tests(matrix);
+    if tests.is_empty() {
+        panic!("{}: crate without generated tests", krate.name());
+    }
+    let loader = krate.loader(matrix);
+
+    let dir = root.join(TEST_DIR);
+    // keep the build output, the decoder dependencies don't change
+    for old in ["decoder", "src"] {
+        let _ = std::fs::remove_dir_all(dir.join(old));
+    }
+    let replaced = copy_decoder(
+        &root.join(sleigh3rust).join(decoder),
+        &dir.join("decoder"),
+        &slaspec,
+    );
+    if replaced == 0 {
+        panic!(
+            "{}: no `\"<path>.slaspec\"` literal found in the decoder sources",
+            decoder
+        );
+    }
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("Cargo.toml"), test_cargo_toml(decoder, loader)).unwrap();
+    std::fs::write(
+        dir.join("src/lib.rs"),
+        lib_rs(Some(decoder), loader, &tests),
+    )
+    .unwrap();
+
+    let status = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .arg("test")
+        .arg("--manifest-path")
+        .arg(dir.join("Cargo.toml"))
+        .args(cargo_args)
+        .status()
+        .expect("Unable to execute cargo");
+    std::process::exit(status.code().unwrap_or(1));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn source_literals() {
+        let mut source = concat!(
+            "let spec = \"../ghidra/Z80/z80.slaspec\";\n",
+            "let other = [\"a.slaspec\", \"b.slaspec\"];\n",
+        )
+        .to_string();
+        assert_eq!(rewrite_source(&mut source, Path::new("/spec/z.slaspec")), 3);
+        assert_eq!(
+            source,
+            concat!(
+                "let spec = \"/spec/z.slaspec\";\n",
+                "let other = [\"/spec/z.slaspec\", \"/spec/z.slaspec\"];\n",
+            )
+        );
+    }
+
+    #[test]
+    fn source_without_literal() {
+        let original = "// the spec.slaspec\"\nfn main() {}\n";
+        let mut source = original.to_string();
+        assert_eq!(rewrite_source(&mut source, Path::new("/spec/z.slaspec")), 0);
+        assert_eq!(source, original);
+    }
+
+    #[test]
+    fn manifest_paths() {
+        let manifest = concat!(
+            "[package]\n",
+            "name = \"z80\"\n",
+            "[dependencies]\n",
+            "sleigh4rust = { path = \"../sleigh4rust\" }\n",
+            "serde = \"1\"\n",
+            "[build-dependencies]\n",
+            "sleigh2rust = { path = \"../sleigh2rust\", features = [\"x\"] }\n",
+        );
+        let decoder_dir = Path::new("/nonexistent/sleigh3rust/z80");
+        let manifest: toml::Table =
+            toml::from_str(&rewrite_manifest(manifest, decoder_dir)).unwrap();
+        let path =
+            |table: &str, name: &str| manifest[table][name]["path"].as_str().unwrap().to_string();
+        assert_eq!(
+            path("dependencies", "sleigh4rust"),
+            decoder_dir.join("../sleigh4rust").display().to_string()
+        );
+        assert_eq!(
+            path("build-dependencies", "sleigh2rust"),
+            decoder_dir.join("../sleigh2rust").display().to_string()
+        );
+        assert_eq!(manifest["dependencies"]["serde"].as_str(), Some("1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "inherited from the workspace")]
+    fn manifest_workspace_dependency() {
+        rewrite_manifest(
+            "[dependencies]\nserde = { workspace = true }\n",
+            Path::new("/nonexistent"),
+        );
+    }
+}
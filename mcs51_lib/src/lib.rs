@@ -0,0 +1,90 @@
+use std::io::Read;
+
+use common_lib::{hex_units, test_token_or_undefined};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cpu {
+    I8051,
+    /// Extended instructions escaped by `A5` in binary mode
+    I80251,
+    /// 24 bits addresses in the contiguous mode
+    I80390,
+    Mx51,
+}
+impl Cpu {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "8051" => Cpu::I8051,
+            "80251" => Cpu::I80251,
+            "80390" => Cpu::I80390,
+            "mx51" => Cpu::Mx51,
+            _ => panic!("Invalid 8051 cpu `{}`", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub cpu: Vec<Cpu>,
+    /// Address in the code space
+    pub addr: u32,
+    pub token: Vec<u8>,
+    /// `None` if the opcode is undefined
+    pub result: Option<String>,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct InstructionSerialized {
+    cpu: String,
+    addr: u32,
+    token: String,
+    result: Option<String>,
+}
+impl From<InstructionSerialized> for Instruction {
+    fn from(value: InstructionSerialized) -> Self {
+        Self {
+            cpu: value.cpu.split_whitespace().map(Cpu::from_name).collect(),
+            addr: value.addr,
+            token: hex_units(&value.token, 2),
+            result: value.result,
+        }
+    }
+}
+
+/// All the 256 8051 opcodes, followed by the SFR and bit names, the
+/// `AJMP`/`ACALL` page boundaries and the 80390 and 80251 extensions.
+pub const OPCODE_INSTRUCTION_FILE: &str = "../assets/mcs51/opcode.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
+    common_lib::tests_from_file::<InstructionSerialized, _, _>(file)
+}
+
+pub use common_lib::ParseFn;
+
+pub fn tests_instruction_from_file<A>(file: &str, cpu: Cpu, parse: ParseFn<A>)
+where
+    A: TryFrom<u32> + Into<u32> + core::fmt::Debug,
+    <A as TryFrom<u32>>::Error: core::fmt::Debug,
+{
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        if !instruction.cpu.contains(&cpu) {
+            continue;
+        }
+        let token = &instruction.token;
+        let Some(next_addr) = test_token_or_undefined(
+            token,
+            A::try_from(instruction.addr).unwrap(),
+            instruction.result.as_deref(),
+            parse,
+        ) else {
+            continue;
+        };
+        assert_eq!(
+            next_addr.into(),
+            instruction.addr + token.len() as u32,
+            "at instruction {:x?}",
+            token
+        );
+    }
+}
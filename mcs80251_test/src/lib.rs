@@ -0,0 +1,11 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use mcs51_lib::*;
+    use mcs80251::*;
+
+    #[test]
+    fn opcode_instructions() {
+        tests_instruction_from_file(OPCODE_INSTRUCTION_FILE, Cpu::I80251, parse_default)
+    }
+}
@@ -0,0 +1,27 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use mips32r6be::*;
+    use mips_lib::*;
+
+    #[test]
+    fn basic_instructions() {
+        tests_instruction_from_file(
+            BASIC_INSTRUCTION_FILE,
+            Isa::Mips32R6,
+            true,
+            parse_default,
+            None,
+            None,
+        )
+    }
+    #[test]
+    fn delay_slot() {
+        tests_delay_slot_from_file(
+            DELAY_SLOT_INSTRUCTION_FILE,
+            Isa::Mips32R6,
+            true,
+            parse_default,
+        )
+    }
+}
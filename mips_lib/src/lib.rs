@@ -0,0 +1,201 @@
+use std::io::Read;
+
+use common_lib::{hex_units, test_token};
+use serde::Deserialize;
+
+/// The ISA revision implemented by the generated crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Isa {
+    Mips32,
+    Mips32R6,
+    Mips64,
+}
+impl Isa {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "mips32" => Isa::Mips32,
+            "mips32r6" => Isa::Mips32R6,
+            "mips64" => Isa::Mips64,
+            _ => panic!("Invalid MIPS isa `{}`", name),
+        }
+    }
+}
+
+/// The ISA mode, selected by the context when decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Mode {
+    Mips,
+    Mips16,
+    MicroMips,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Word(u32),
+    /// MIPS16e and microMIPS are made of one or two halfwords
+    HalfWords(Vec<u16>),
+}
+impl Instruction {
+    fn new(mode: Mode, token: &str) -> Self {
+        match mode {
+            Mode::Mips => Self::Word(u32::from_str_radix(token, 16).unwrap()),
+            Mode::Mips16 | Mode::MicroMips => Self::HalfWords(hex_units(token, 4)),
+        }
+    }
+    pub fn to_tokens(&self, big_endian: bool) -> Vec<u8> {
+        match (self, big_endian) {
+            (Self::Word(x), true) => x.to_be_bytes().to_vec(),
+            (Self::Word(x), false) => x.to_le_bytes().to_vec(),
+            (Self::HalfWords(x), true) => x.iter().flat_map(|x| x.to_be_bytes()).collect(),
+            (Self::HalfWords(x), false) => x.iter().flat_map(|x| x.to_le_bytes()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Test {
+    pub isa: Vec<Isa>,
+    pub mode: Mode,
+    pub addr: u64,
+    pub instruction: Instruction,
+    pub result: String,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct TestSerialized {
+    isa: String,
+    mode: Mode,
+    addr: u64,
+    token: String,
+    result: String,
+}
+impl From<TestSerialized> for Test {
+    fn from(value: TestSerialized) -> Self {
+        Self {
+            isa: value.isa.split_whitespace().map(Isa::from_name).collect(),
+            mode: value.mode,
+            addr: value.addr,
+            instruction: Instruction::new(value.mode, &value.token),
+            result: value.result,
+        }
+    }
+}
+
+/// A branch and the instruction in its delay slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelaySlotTest {
+    pub isa: Vec<Isa>,
+    pub addr: u64,
+    pub branch: u32,
+    pub slot: u32,
+    pub branch_result: String,
+    pub slot_result: String,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct DelaySlotTestSerialized {
+    isa: String,
+    addr: u64,
+    branch: String,
+    slot: String,
+    branch_result: String,
+    slot_result: String,
+}
+impl From<DelaySlotTestSerialized> for DelaySlotTest {
+    fn from(value: DelaySlotTestSerialized) -> Self {
+        Self {
+            isa: value.isa.split_whitespace().map(Isa::from_name).collect(),
+            addr: value.addr,
+            branch: u32::from_str_radix(&value.branch, 16).unwrap(),
+            slot: u32::from_str_radix(&value.slot, 16).unwrap(),
+            branch_result: value.branch_result,
+            slot_result: value.slot_result,
+        }
+    }
+}
+
+pub const BASIC_INSTRUCTION_FILE: &str = "../assets/mips/basic.csv";
+pub const DELAY_SLOT_INSTRUCTION_FILE: &str = "../assets/mips/delay_slot.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Test>> {
+    common_lib::tests_from_file::<TestSerialized, _, _>(file)
+}
+pub fn delay_slot_tests_from_file<R: Read>(
+    file: R,
+) -> impl Iterator<Item = csv::Result<DelaySlotTest>> {
+    common_lib::tests_from_file::<DelaySlotTestSerialized, _, _>(file)
+}
+
+pub use common_lib::ParseFn;
+
+/// Returns the next instruction address.
+fn test_tokens<A>(token: &[u8], addr: u64, result: &str, parse: ParseFn<A>) -> u64
+where
+    A: TryFrom<u64> + Into<u64> + core::fmt::Debug,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    test_token(token, A::try_from(addr).unwrap(), result, parse).into()
+}
+
+pub fn tests_instruction_from_file<A>(
+    file: &str,
+    isa: Isa,
+    big_endian: bool,
+    parse_default: ParseFn<A>,
+    parse_mips16: Option<ParseFn<A>>,
+    parse_micromips: Option<ParseFn<A>>,
+) where
+    A: TryFrom<u64> + Into<u64> + core::fmt::Debug,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let test_file = std::fs::File::open(file).unwrap();
+    let tests = tests_from_file(test_file);
+    for test in tests.map(Result::unwrap) {
+        if !test.isa.contains(&isa) {
+            continue;
+        }
+        //only parse the compressed modes if the crate have it
+        let parse = match test.mode {
+            Mode::Mips => parse_default,
+            Mode::Mips16 => match parse_mips16 {
+                Some(parse) => parse,
+                None => continue,
+            },
+            Mode::MicroMips => match parse_micromips {
+                Some(parse) => parse,
+                None => continue,
+            },
+        };
+        let token = test.instruction.to_tokens(big_endian);
+        let next_addr = test_tokens(&token, test.addr, &test.result, parse);
+        assert_eq!(
+            next_addr,
+            test.addr + token.len() as u64,
+            "at instruction {:x?}",
+            test.instruction
+        );
+    }
+}
+
+/// The branch is decoded with the delay slot instruction after it, it need
+/// to consume only its own word, the delay slot is decoded on its own.
+pub fn tests_delay_slot_from_file<A>(
+    file: &str,
+    isa: Isa,
+    big_endian: bool,
+    parse_default: ParseFn<A>,
+) where
+    A: TryFrom<u64> + Into<u64> + core::fmt::Debug,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let test_file = std::fs::File::open(file).unwrap();
+    let tests = delay_slot_tests_from_file(test_file);
+    for test in tests.map(Result::unwrap) {
+        if !test.isa.contains(&isa) {
+            continue;
+        }
+        let branch = Instruction::Word(test.branch).to_tokens(big_endian);
+        let slot = Instruction::Word(test.slot).to_tokens(big_endian);
+        let pair = [branch.as_slice(), slot.as_slice()].concat();
+        let next_addr = test_tokens(&pair, test.addr, &test.branch_result, parse_default);
+        assert_eq!(next_addr, test.addr + 4, "branch consumed the delay slot");
+        test_tokens(&slot, test.addr + 4, &test.slot_result, parse_default);
+    }
+}
@@ -0,0 +1,58 @@
+use std::io::Read;
+
+use common_lib::{hex_units, test_token_or_undefined};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cpu {
+    /// Only the documented NMOS opcodes
+    Mos6502,
+    Wdc65c02,
+}
+
+/// The same token decoded by each cpu, `None` if the opcode is undefined.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Instruction {
+    pub addr: u16,
+    token: String,
+    pub result_6502: Option<String>,
+    pub result_65c02: Option<String>,
+}
+impl Instruction {
+    pub fn to_tokens(&self) -> Vec<u8> {
+        hex_units(&self.token, 2)
+    }
+    pub fn result(&self, cpu: Cpu) -> Option<&str> {
+        match cpu {
+            Cpu::Mos6502 => self.result_6502.as_deref(),
+            Cpu::Wdc65c02 => self.result_65c02.as_deref(),
+        }
+    }
+}
+
+/// All the 256 opcodes, followed by branches around page boundaries.
+pub const OPCODE_INSTRUCTION_FILE: &str = "../assets/mos6502/opcode.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
+    common_lib::tests_from_file::<Instruction, _, _>(file)
+}
+
+pub type ParseFn = common_lib::ParseFn<u16>;
+
+pub fn tests_instruction_from_file(file: &str, cpu: Cpu, parse: ParseFn) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        let token = instruction.to_tokens();
+        let addr = instruction.addr;
+        let Some(next_addr) = test_token_or_undefined(&token, addr, instruction.result(cpu), parse)
+        else {
+            continue;
+        };
+        assert_eq!(
+            next_addr,
+            addr.wrapping_add(token.len() as u16),
+            "at instruction {:x?}",
+            token
+        );
+    }
+}
@@ -0,0 +1,11 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use mos6502::*;
+    use mos6502_lib::*;
+
+    #[test]
+    fn opcode_instructions() {
+        tests_instruction_from_file(OPCODE_INSTRUCTION_FILE, Cpu::Mos6502, parse_default)
+    }
+}
@@ -0,0 +1,11 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use pa_risc32be::*;
+    use token_lib::*;
+
+    #[test]
+    fn basic_instructions() {
+        tests_instruction_from_file(PA_RISC_INSTRUCTION_FILE, 1, parse_default)
+    }
+}
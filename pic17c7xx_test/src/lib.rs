@@ -0,0 +1,11 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use pic17c7xx::*;
+    use pic_lib::*;
+
+    #[test]
+    fn basic_instructions() {
+        tests_words_instruction_from_file(BASIC_INSTRUCTION_FILE, Core::Pic17, parse_default)
+    }
+}
@@ -0,0 +1,89 @@
+use std::io::Read;
+
+use common_lib::{hex_units, test_token_or_undefined};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Family {
+    /// Rows shared by all the cores
+    All,
+    /// dsPIC30F and dsPIC33, with the DSP engine
+    Dspic,
+    /// Without the DSP engine, its `0xc0` to `0xcf` opcodes are undefined
+    Pic24,
+}
+
+/// Each 24 bits instruction word is stored in 4 bytes, the last is the
+/// unimplemented phantom byte, and advance the PC by 2 units.
+pub const PC_UNITS_PER_WORD: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub family: Family,
+    /// In PC units
+    pub addr: u32,
+    /// One or two 24 bits words, eg: `goto`, `call` and `do` use two
+    pub words: Vec<u32>,
+    /// `None` if the opcode is undefined
+    pub result: Option<String>,
+}
+impl Instruction {
+    pub fn to_tokens(&self) -> Vec<u8> {
+        self.words
+            .iter()
+            .flat_map(|word| {
+                let [b0, b1, b2, phantom] = word.to_le_bytes();
+                assert_eq!(phantom, 0, "instruction words are 24 bits");
+                [b0, b1, b2, 0]
+            })
+            .collect()
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct InstructionSerialized {
+    family: Family,
+    addr: u32,
+    token: String,
+    result: Option<String>,
+}
+impl From<InstructionSerialized> for Instruction {
+    fn from(value: InstructionSerialized) -> Self {
+        Self {
+            family: value.family,
+            addr: value.addr,
+            words: hex_units(&value.token, 6),
+            result: value.result,
+        }
+    }
+}
+
+pub const BASIC_INSTRUCTION_FILE: &str = "../assets/pic24/basic.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
+    common_lib::tests_from_file::<InstructionSerialized, _, _>(file)
+}
+
+pub type ParseFn = common_lib::ParseFn<u32>;
+
+pub fn tests_instruction_from_file(file: &str, family: Family, parse: ParseFn) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        if instruction.family != Family::All && instruction.family != family {
+            continue;
+        }
+        let token = instruction.to_tokens();
+        let addr = instruction.addr;
+        let Some(next_addr) =
+            test_token_or_undefined(&token, addr, instruction.result.as_deref(), parse)
+        else {
+            continue;
+        };
+        assert_eq!(
+            next_addr,
+            addr + instruction.words.len() as u32 * PC_UNITS_PER_WORD,
+            "at instruction {:x?}",
+            token
+        );
+    }
+}
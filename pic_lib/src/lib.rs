@@ -1,5 +1,6 @@
 use std::io::Read;
 
+use common_lib::{hex_units, test_token_or_undefined};
 use serde::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -32,11 +33,9 @@ pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Ins
     TestsFromFile(csv::Reader::from_reader(file).into_deserialize::<Instruction>())
 }
 
-pub fn tests_instruction_from_file(
-    file: &str,
-    big_endian: bool,
-    parse: fn(&[u8], u16) -> Option<(u16, String)>,
-) {
+pub use common_lib::ParseFn;
+
+pub fn tests_instruction_from_file(file: &str, big_endian: bool, parse: ParseFn<u16>) {
     let test_file = std::fs::File::open(file).unwrap();
     let instructions = tests_from_file(test_file);
     for instruction in instructions.map(Result::unwrap) {
@@ -52,3 +51,106 @@ pub fn tests_instruction_from_file(
         assert_eq!(next_addr, instruction.addr + 1);
     }
 }
+
+/// The 14 bits midrange and the 16 bits cores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Core {
+    /// Classic midrange, the enhanced `ADDFSR`, `BRA`, `MOVLP` and `MOVIW`
+    /// are the don't care bits of its `MOVLW` and `ADDLW`
+    Pic16,
+    /// Enhanced midrange, `moviw`, `movwi`, `brw`, `addfsr`
+    Pic16f,
+    Pic17,
+    Pic18,
+}
+impl Core {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "pic16" => Core::Pic16,
+            "pic16f" => Core::Pic16f,
+            "pic17" => Core::Pic17,
+            "pic18" => Core::Pic18,
+            _ => panic!("Invalid PIC core `{}`", name),
+        }
+    }
+    /// The PIC18 program counter address bytes, the others address words
+    pub fn pc_units_per_word(self) -> u32 {
+        match self {
+            Core::Pic18 => 2,
+            Core::Pic16 | Core::Pic16f | Core::Pic17 => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordsInstruction {
+    pub core: Vec<Core>,
+    /// In PC units
+    pub addr: u32,
+    /// One word, or two for the PIC18 `movff`, `lfsr`, `goto` and `call`
+    pub words: Vec<u16>,
+    /// `None` if the opcode is undefined
+    pub result: Option<String>,
+}
+impl WordsInstruction {
+    fn to_tokens(&self) -> Vec<u8> {
+        self.words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect()
+    }
+}
+#[derive(Debug, Clone, Deserialize)]
+struct WordsInstructionSerialized {
+    core: String,
+    addr: u32,
+    token: String,
+    result: Option<String>,
+}
+impl From<WordsInstructionSerialized> for WordsInstruction {
+    fn from(value: WordsInstructionSerialized) -> Self {
+        Self {
+            core: value.core.split_whitespace().map(Core::from_name).collect(),
+            addr: value.addr,
+            words: hex_units(&value.token, 4),
+            result: value.result,
+        }
+    }
+}
+
+pub const BASIC_INSTRUCTION_FILE: &str = "../assets/pic/basic.csv";
+pub fn words_tests_from_file<R: Read>(
+    file: R,
+) -> impl Iterator<Item = csv::Result<WordsInstruction>> {
+    common_lib::tests_from_file::<WordsInstructionSerialized, _, _>(file)
+}
+
+pub fn tests_words_instruction_from_file<A>(file: &str, core: Core, parse: ParseFn<A>)
+where
+    A: TryFrom<u32> + Into<u32> + core::fmt::Debug,
+    <A as TryFrom<u32>>::Error: core::fmt::Debug,
+{
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = words_tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        if !instruction.core.contains(&core) {
+            continue;
+        }
+        let token = instruction.to_tokens();
+        let addr = instruction.addr;
+        let Some(next_addr) = test_token_or_undefined(
+            &token,
+            A::try_from(addr).unwrap(),
+            instruction.result.as_deref(),
+            parse,
+        ) else {
+            continue;
+        };
+        assert_eq!(
+            next_addr.into(),
+            addr + instruction.words.len() as u32 * core.pc_units_per_word(),
+            "at instruction {:x?}",
+            token
+        );
+    }
+}
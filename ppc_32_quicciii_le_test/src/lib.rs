@@ -0,0 +1,17 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use ppc_32_quicciii_le::*;
+    use ppc_lib::*;
+
+    #[test]
+    fn basic_instructions() {
+        tests_instruction_from_file(
+            BASIC_INSTRUCTION_FILE,
+            &[Variant::QuiccIII],
+            false,
+            parse_default,
+            None,
+        )
+    }
+}
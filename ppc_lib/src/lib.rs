@@ -0,0 +1,129 @@
+use std::io::Read;
+
+use common_lib::test_token;
+use serde::Deserialize;
+
+/// Optional instruction sets, a row tagged with variants is only tested on
+/// crates that implement all of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// 64-bit only forms
+    Ppc64,
+    Ppc4xx,
+    Altivec,
+    /// Variable Length Encoding, decoded with the `vle` context set
+    Vle,
+    QuiccIII,
+}
+impl Variant {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "64" => Variant::Ppc64,
+            "4xx" => Variant::Ppc4xx,
+            "altivec" => Variant::Altivec,
+            "vle" => Variant::Vle,
+            "quicciii" => Variant::QuiccIII,
+            _ => panic!("Invalid PowerPC variant `{}`", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Word(u32),
+    /// VLE `se_*` instructions
+    HalfWord(u16),
+}
+impl Instruction {
+    fn new(token: &str) -> Self {
+        match token.len() {
+            4 => Self::HalfWord(u16::from_str_radix(token, 16).unwrap()),
+            8 => Self::Word(u32::from_str_radix(token, 16).unwrap()),
+            _ => panic!("Invalid PowerPC token `{}`", token),
+        }
+    }
+    pub fn to_tokens(&self, big_endian: bool) -> Vec<u8> {
+        match (self, big_endian) {
+            (Self::Word(x), true) => x.to_be_bytes().to_vec(),
+            (Self::Word(x), false) => x.to_le_bytes().to_vec(),
+            (Self::HalfWord(x), true) => x.to_be_bytes().to_vec(),
+            (Self::HalfWord(x), false) => x.to_le_bytes().to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Test {
+    pub variants: Vec<Variant>,
+    pub addr: u64,
+    pub instruction: Instruction,
+    pub result: String,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct TestSerialized {
+    variants: String,
+    addr: u64,
+    token: String,
+    result: String,
+}
+impl From<TestSerialized> for Test {
+    fn from(value: TestSerialized) -> Self {
+        Self {
+            variants: value
+                .variants
+                .split_whitespace()
+                .map(Variant::from_name)
+                .collect(),
+            addr: value.addr,
+            instruction: Instruction::new(&value.token),
+            result: value.result,
+        }
+    }
+}
+
+pub const BASIC_INSTRUCTION_FILE: &str = "../assets/ppc/basic.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Test>> {
+    common_lib::tests_from_file::<TestSerialized, _, _>(file)
+}
+
+pub use common_lib::ParseFn;
+
+/// `variants` are the optional instruction sets implemented by the crate,
+/// [Variant::Vle] rows are decoded with `parse_vle`, so the crates with VLE
+/// need to include it in both.
+pub fn tests_instruction_from_file<A>(
+    file: &str,
+    variants: &[Variant],
+    big_endian: bool,
+    parse_default: ParseFn<A>,
+    parse_vle: Option<ParseFn<A>>,
+) where
+    A: TryFrom<u64> + Into<u64> + core::fmt::Debug,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let test_file = std::fs::File::open(file).unwrap();
+    let tests = tests_from_file(test_file);
+    for test in tests.map(Result::unwrap) {
+        if !test
+            .variants
+            .iter()
+            .all(|variant| variants.contains(variant))
+        {
+            continue;
+        }
+        let parse = if test.variants.contains(&Variant::Vle) {
+            parse_vle.expect("VLE crates need the vle parser")
+        } else {
+            parse_default
+        };
+        let token = test.instruction.to_tokens(big_endian);
+        let next_addr = test_token(&token, A::try_from(test.addr).unwrap(), &test.result, parse);
+        // VLE mix 16 and 32 bits instructions
+        assert_eq!(
+            next_addr.into(),
+            test.addr + token.len() as u64,
+            "at instruction {:x?}",
+            test.instruction
+        );
+    }
+}
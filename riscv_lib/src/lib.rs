@@ -0,0 +1,145 @@
+use std::io::Read;
+
+use common_lib::test_token;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Xlen {
+    Rv32,
+    Rv64,
+}
+impl Xlen {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "" => None,
+            "32" => Some(Xlen::Rv32),
+            "64" => Some(Xlen::Rv64),
+            _ => panic!("Invalid RISC-V xlen `{}`", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extension {
+    I,
+    M,
+    A,
+    F,
+    D,
+    /// Compressed 16 bits instructions
+    C,
+    Zicsr,
+    Zifencei,
+}
+impl Extension {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "I" => Extension::I,
+            "M" => Extension::M,
+            "A" => Extension::A,
+            "F" => Extension::F,
+            "D" => Extension::D,
+            "C" => Extension::C,
+            "Zicsr" => Extension::Zicsr,
+            "Zifencei" => Extension::Zifencei,
+            _ => panic!("Invalid RISC-V extension `{}`", name),
+        }
+    }
+}
+/// The `G` general purpose ISA with the `C` compressed extension
+pub const RV_GC: &[Extension] = &[
+    Extension::I,
+    Extension::M,
+    Extension::A,
+    Extension::F,
+    Extension::D,
+    Extension::C,
+    Extension::Zicsr,
+    Extension::Zifencei,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Word(u32),
+    Compressed(u16),
+}
+impl Instruction {
+    fn new(token: &str) -> Self {
+        match token.len() {
+            4 => Self::Compressed(u16::from_str_radix(token, 16).unwrap()),
+            8 => Self::Word(u32::from_str_radix(token, 16).unwrap()),
+            _ => panic!("Invalid RISC-V token `{}`", token),
+        }
+    }
+    pub fn to_tokens(&self) -> Vec<u8> {
+        match self {
+            Self::Word(x) => x.to_le_bytes().to_vec(),
+            Self::Compressed(x) => x.to_le_bytes().to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Test {
+    /// Only valid on this xlen, or both if none
+    pub xlen: Option<Xlen>,
+    pub extension: Extension,
+    pub addr: u64,
+    pub instruction: Instruction,
+    pub result: String,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct TestSerialized {
+    xlen: String,
+    extension: String,
+    addr: u64,
+    token: String,
+    result: String,
+}
+impl From<TestSerialized> for Test {
+    fn from(value: TestSerialized) -> Self {
+        Self {
+            xlen: Xlen::from_name(&value.xlen),
+            extension: Extension::from_name(&value.extension),
+            addr: value.addr,
+            instruction: Instruction::new(&value.token),
+            result: value.result,
+        }
+    }
+}
+
+pub const BASIC_INSTRUCTION_FILE: &str = "../assets/riscv/basic.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Test>> {
+    common_lib::tests_from_file::<TestSerialized, _, _>(file)
+}
+
+pub use common_lib::ParseFn;
+
+pub fn tests_instruction_from_file<A>(
+    file: &str,
+    xlen: Xlen,
+    extensions: &[Extension],
+    parse: ParseFn<A>,
+) where
+    A: TryFrom<u64> + Into<u64> + core::fmt::Debug,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let test_file = std::fs::File::open(file).unwrap();
+    let tests = tests_from_file(test_file);
+    for test in tests.map(Result::unwrap) {
+        if test.xlen.is_some_and(|test_xlen| test_xlen != xlen)
+            || !extensions.contains(&test.extension)
+        {
+            continue;
+        }
+        let token = test.instruction.to_tokens();
+        let next_addr = test_token(&token, A::try_from(test.addr).unwrap(), &test.result, parse);
+        // compressed instructions are 2 bytes long
+        assert_eq!(
+            next_addr.into(),
+            test.addr + token.len() as u64,
+            "at instruction {:x?}",
+            test.instruction
+        );
+    }
+}
@@ -0,0 +1,15 @@
+// Generated from matrix.toml, do not edit.
+#[cfg(test)]
+mod test {
+    use sh_2::*;
+    use superh4_lib::*;
+
+    #[test]
+    fn mov() {
+        tests_instruction_from_file(MOV_INSTRUCTION_FILE, Cpu::Sh2, true, parse_default)
+    }
+    #[test]
+    fn basic() {
+        tests_instruction_from_file(BASIC_INSTRUCTION_FILE, Cpu::Sh2, true, parse_default)
+    }
+}
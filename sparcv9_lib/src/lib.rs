@@ -0,0 +1,39 @@
+use std::io::Read;
+
+use common_lib::test_token;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Instruction {
+    pub addr: u64,
+    token: u32,
+    pub result: String,
+}
+impl Instruction {
+    fn to_tokens(&self) -> [u8; 4] {
+        self.token.to_be_bytes()
+    }
+}
+
+pub const RANDOM_32_INSTRUCTION_FILE: &str = "../assets/sparcv9/random_32.csv";
+pub const RANDOM_64_INSTRUCTION_FILE: &str = "../assets/sparcv9/random_64.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
+    common_lib::tests_from_file::<Instruction, _, _>(file)
+}
+
+pub use common_lib::ParseFn;
+
+pub fn tests_instruction_from_file<A>(file: &str, parse: ParseFn<A>)
+where
+    A: TryFrom<u64> + Into<u64> + core::fmt::Debug,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        let token = instruction.to_tokens();
+        let addr = A::try_from(instruction.addr).unwrap();
+        let next_addr = test_token(&token, addr, &instruction.result, parse);
+        assert_eq!(next_addr.into(), instruction.addr + 4);
+    }
+}
@@ -0,0 +1,17 @@
+#[cfg(test)]
+mod test {
+    use superh4_lib::*;
+
+    #[test]
+    fn mov() {
+        cross_check_from_file(
+            MOV_INSTRUCTION_FILE,
+            superh4_be::parse_default,
+            superh4_le::parse_default,
+        )
+    }
+    #[test]
+    fn exhaustive() {
+        cross_check_exhaustive(superh4_be::parse_default, superh4_le::parse_default)
+    }
+}
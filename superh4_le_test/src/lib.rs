@@ -1,11 +1,15 @@
+// Generated from matrix.toml, do not edit.
 #[cfg(test)]
 mod test {
-
     use superh4_le::*;
     use superh4_lib::*;
 
     #[test]
     fn random() {
-        tests_instruction_from_file(MOV_INSTRUCTION_FILE, false, parse_default)
+        tests_instruction_from_file(MOV_INSTRUCTION_FILE, Cpu::Sh4, false, parse_default)
+    }
+    #[test]
+    fn basic() {
+        tests_instruction_from_file(BASIC_INSTRUCTION_FILE, Cpu::Sh4, false, parse_default)
     }
 }
@@ -1,54 +1,128 @@
 use std::io::Read;
 
+use common_lib::{hex_units, test_token};
 use serde::Deserialize;
 
-#[derive(Debug, Clone, Deserialize)]
+/// The CPU level of the instruction, `sh1 ⊂ sh2 ⊂ sh2a` and `sh2 ⊂ sh4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Cpu {
+    Sh1,
+    Sh2,
+    Sh2a,
+    Sh4,
+}
+impl Cpu {
+    /// If the instructions of `level` are implemented by this CPU
+    pub fn includes(self, level: Cpu) -> bool {
+        match (self, level) {
+            (_, Cpu::Sh1) => true,
+            (Cpu::Sh1, _) => false,
+            (_, Cpu::Sh2) => true,
+            (cpu, level) => cpu == level,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Instruction {
+    pub cpu: Cpu,
     pub addr: u32,
-    token: u16,
+    /// One halfword, or two for the SH-2A 32 bits instructions
+    token: Vec<u16>,
     pub result: String,
 }
 impl Instruction {
-    fn to_tokens(&self, big_endian: bool) -> [u8; 2] {
-        if big_endian {
-            self.token.to_be_bytes()
-        } else {
-            self.token.to_le_bytes()
-        }
+    fn to_tokens(&self, big_endian: bool) -> Vec<u8> {
+        self.token
+            .iter()
+            .flat_map(|half| {
+                if big_endian {
+                    half.to_be_bytes()
+                } else {
+                    half.to_le_bytes()
+                }
+            })
+            .collect()
     }
 }
-struct TestsFromFile<R>(csv::DeserializeRecordsIntoIter<R, Instruction>);
-impl<R: Read> Iterator for TestsFromFile<R> {
-    type Item = csv::Result<Instruction>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+#[derive(Debug, Clone, Deserialize)]
+struct InstructionSerialized {
+    cpu: Cpu,
+    addr: u32,
+    token: String,
+    result: String,
+}
+impl From<InstructionSerialized> for Instruction {
+    fn from(value: InstructionSerialized) -> Self {
+        Self {
+            cpu: value.cpu,
+            addr: value.addr,
+            token: hex_units(&value.token, 4),
+            result: value.result,
+        }
     }
 }
 
 pub const MOV_INSTRUCTION_FILE: &str = "../assets/superh4/mov.csv";
+pub const BASIC_INSTRUCTION_FILE: &str = "../assets/superh4/basic.csv";
 pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
-    TestsFromFile(csv::Reader::from_reader(file).into_deserialize::<Instruction>())
+    common_lib::tests_from_file::<InstructionSerialized, _, _>(file)
 }
 
-pub fn tests_instruction_from_file(
-    file: &str,
-    big_endian: bool,
-    parse: fn(&[u8], u32) -> Option<(u32, String)>,
-) {
+pub type ParseFn = common_lib::ParseFn<u32>;
+
+pub fn tests_instruction_from_file(file: &str, cpu: Cpu, big_endian: bool, parse: ParseFn) {
     let test_file = std::fs::File::open(file).unwrap();
     let instructions = tests_from_file(test_file);
     for instruction in instructions.map(Result::unwrap) {
+        if !cpu.includes(instruction.cpu) {
+            continue;
+        }
         let token = instruction.to_tokens(big_endian);
         let addr = instruction.addr;
-        let Some((next_addr, result)) = parse(&token, addr) else {
-                panic!(
-                    "Unable to parse the {:x?} with expected output `{}`",
-                    &token,
-                    &addr,
-                );
-            };
-        assert_eq!(result, instruction.result);
+        let next_addr = test_token(&token, addr, &instruction.result, parse);
         assert_eq!(next_addr, addr + token.len() as u32);
     }
 }
+
+/// Feed the same instruction to the big and little endian crates, each with its
+/// own byte order, and check that both produce the same disassembly and length,
+/// returns if it was decoded.
+fn cross_check_token(token: &[u16], addr: u32, parse_be: ParseFn, parse_le: ParseFn) -> bool {
+    let be_token: Vec<u8> = token.iter().flat_map(|half| half.to_be_bytes()).collect();
+    let le_token: Vec<u8> = token.iter().flat_map(|half| half.to_le_bytes()).collect();
+    let be_result = parse_be(&be_token, addr);
+    let le_result = parse_le(&le_token, addr);
+    assert_eq!(
+        be_result, le_result,
+        "big/little endian mismatch at token {:04x?} addr {:#x}",
+        token, addr
+    );
+    be_result.is_some()
+}
+
+pub fn cross_check_from_file(file: &str, parse_be: ParseFn, parse_le: ParseFn) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        assert!(
+            cross_check_token(&instruction.token, instruction.addr, parse_be, parse_le),
+            "Unable to parse the {:04x?} with expected output `{}`",
+            instruction.token,
+            instruction.result,
+        );
+    }
+}
+
+pub const CROSS_CHECK_ADDR: u32 = 0x10000;
+/// All SH-4 instructions are 16 bits, so every encoding can be checked.
+pub fn cross_check_exhaustive(parse_be: ParseFn, parse_le: ParseFn) {
+    let mut decoded = 0;
+    for token in 0..=u16::MAX {
+        if cross_check_token(&[token], CROSS_CHECK_ADDR, parse_be, parse_le) {
+            decoded += 1;
+        }
+    }
+    assert_ne!(decoded, 0, "no instruction was decoded");
+}
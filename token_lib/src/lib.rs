@@ -0,0 +1,120 @@
+//! Loader for the architectures without a dedicated token layout, the token
+//! is the memory content in bytes, and the address unit is configurable.
+use std::io::Read;
+use std::ops::RangeInclusive;
+
+use common_lib::{hex_units, test_token};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    /// In address units
+    pub addr: u64,
+    /// The bytes in memory order
+    pub token: Vec<u8>,
+    pub result: String,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct InstructionSerialized {
+    addr: u64,
+    token: String,
+    result: String,
+}
+impl From<InstructionSerialized> for Instruction {
+    fn from(value: InstructionSerialized) -> Self {
+        Self {
+            addr: value.addr,
+            token: hex_units(&value.token, 2),
+            result: value.result,
+        }
+    }
+}
+
+pub const CP1600_INSTRUCTION_FILE: &str = "../assets/cp1600/basic.csv";
+pub const CR16C_INSTRUCTION_FILE: &str = "../assets/cr16/cr16c.csv";
+pub const M8C_INSTRUCTION_FILE: &str = "../assets/m8c/basic.csv";
+pub const MCS96_INSTRUCTION_FILE: &str = "../assets/mcs96/basic.csv";
+/// Nullification `,N` and the condition completers
+pub const PA_RISC_INSTRUCTION_FILE: &str = "../assets/pa_risc/basic.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
+    common_lib::tests_from_file::<InstructionSerialized, _, _>(file)
+}
+
+pub use common_lib::ParseFn;
+
+/// `address_unit` is the number of bytes in each address.
+pub fn tests_instruction_from_file<A>(file: &str, address_unit: u64, parse: ParseFn<A>)
+where
+    A: TryFrom<u64> + Into<u64> + core::fmt::Debug,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        let token = &instruction.token;
+        assert_eq!(
+            token.len() as u64 % address_unit,
+            0,
+            "token {:x?} is not made of address units",
+            token
+        );
+        let addr = instruction.addr;
+        let next_addr = test_token(
+            token,
+            A::try_from(addr).unwrap(),
+            &instruction.result,
+            parse,
+        );
+        assert_eq!(
+            next_addr.into(),
+            addr + token.len() as u64 / address_unit,
+            "at instruction {:x?}",
+            token
+        );
+    }
+}
+
+/// The 16 bits words used to fill the rest of the token after the first.
+pub const FILL_WORD: u16 = 0x0000;
+pub const SWEEP_ADDR: u64 = 0x1000;
+
+/// Decode every first word in `first_words`, followed by [FILL_WORD], and
+/// check that the decoded length is one of `lengths`, in address units, and
+/// that each of them is decoded at least once.
+pub fn check_lengths<A>(
+    first_words: RangeInclusive<u16>,
+    big_endian: bool,
+    address_unit: u64,
+    lengths: &[u64],
+    parse: ParseFn<A>,
+) where
+    A: TryFrom<u64> + Into<u64> + core::fmt::Debug,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let to_bytes = |word: u16| {
+        if big_endian {
+            word.to_be_bytes()
+        } else {
+            word.to_le_bytes()
+        }
+    };
+    let mut decoded = vec![false; lengths.len()];
+    for first in first_words {
+        let token: Vec<u8> = [first, FILL_WORD, FILL_WORD, FILL_WORD]
+            .into_iter()
+            .flat_map(to_bytes)
+            .collect();
+        let addr = A::try_from(SWEEP_ADDR / address_unit).unwrap();
+        let Some((next_addr, _output)) = parse(&token, addr) else {
+            continue;
+        };
+        let len = next_addr.into() - SWEEP_ADDR / address_unit;
+        let Some(index) = lengths.iter().position(|length| *length == len) else {
+            panic!("first word {:#06x} decoded with {} units", first, len);
+        };
+        decoded[index] = true;
+    }
+    for (length, decoded) in lengths.iter().zip(decoded) {
+        assert!(decoded, "no instruction decoded with {} units", length);
+    }
+}
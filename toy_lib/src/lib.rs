@@ -0,0 +1,206 @@
+//! Conformance checks for the Ghidra toy specs, each one exercise a single
+//! SLEIGH feature, by comparing the crate with a sibling spec that differs
+//! only by this feature. All the first halfwords are checked, followed by a
+//! fixed second halfword, for the instructions that take two.
+
+pub use common_lib::ParseFn;
+
+pub const TRAILING_HALFWORD: u16 = 0x1234;
+pub const BASE_ADDR: u64 = 0x1000;
+/// The toy specs decode many more first halfwords than this, so two specs
+/// that decode nothing can't pass the checks.
+pub const MIN_DECODED: usize = 0x100;
+
+fn check_decoded(decoded: usize) {
+    assert!(
+        decoded >= MIN_DECODED,
+        "only {} first halfwords decoded",
+        decoded
+    );
+}
+
+pub fn to_tokens(first: u16, big_endian: bool) -> Vec<u8> {
+    [first, TRAILING_HALFWORD]
+        .iter()
+        .flat_map(|half| {
+            if big_endian {
+                half.to_be_bytes()
+            } else {
+                half.to_le_bytes()
+            }
+        })
+        .collect()
+}
+
+/// Returns the instruction length in address units and the disassembly.
+fn decode<A>(parse: ParseFn<A>, token: &[u8], addr: u64) -> Option<(u64, String)>
+where
+    A: TryFrom<u64> + Into<u64>,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    parse(token, A::try_from(addr).unwrap()).map(|(next, output)| (next.into() - addr, output))
+}
+
+/// `endian` big and little endian specs decode the same instructions.
+pub fn check_endianness<A>(parse_be: ParseFn<A>, parse_le: ParseFn<A>)
+where
+    A: TryFrom<u64> + Into<u64>,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let mut decoded = 0;
+    for first in 0..=u16::MAX {
+        let be = decode(parse_be, &to_tokens(first, true), BASE_ADDR);
+        let le = decode(parse_le, &to_tokens(first, false), BASE_ADDR);
+        decoded += usize::from(be.is_some());
+        assert_eq!(be, le, "endian mismatch at first halfword {:#06x}", first);
+    }
+    check_decoded(decoded);
+}
+
+/// `alignment`, instructions are only decoded at aligned addresses, and
+/// decode like the unaligned spec there.
+pub fn check_alignment<A>(
+    alignment: u64,
+    big_endian: bool,
+    parse_aligned: ParseFn<A>,
+    parse_unaligned: ParseFn<A>,
+) where
+    A: TryFrom<u64> + Into<u64>,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let mut decoded = 0;
+    for first in 0..=u16::MAX {
+        let token = to_tokens(first, big_endian);
+        let aligned = decode(parse_aligned, &token, BASE_ADDR);
+        let unaligned = decode(parse_unaligned, &token, BASE_ADDR);
+        decoded += usize::from(aligned.is_some());
+        assert_eq!(
+            aligned, unaligned,
+            "mismatch at first halfword {:#06x}",
+            first
+        );
+        for offset in 1..alignment {
+            assert_eq!(
+                decode(parse_aligned, &token, BASE_ADDR + offset),
+                None,
+                "decoded unaligned first halfword {:#06x} at {:#x}",
+                first,
+                BASE_ADDR + offset
+            );
+        }
+    }
+    check_decoded(decoded);
+}
+
+/// `wordsize`, the address unit is `word_size` bytes, so the same
+/// instructions advance the address by the length divided by the word size.
+pub fn check_wordsize<A, B>(
+    word_size: u64,
+    big_endian: bool,
+    parse_words: ParseFn<A>,
+    parse_bytes: ParseFn<B>,
+) where
+    A: TryFrom<u64> + Into<u64>,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+    B: TryFrom<u64> + Into<u64>,
+    <B as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let mut decoded = 0;
+    for first in 0..=u16::MAX {
+        let token = to_tokens(first, big_endian);
+        let words = decode(parse_words, &token, BASE_ADDR / word_size);
+        let bytes = decode(parse_bytes, &token, BASE_ADDR);
+        decoded += usize::from(bytes.is_some());
+        assert_eq!(
+            words.map(|(len, _)| len * word_size),
+            bytes.map(|(len, _)| len),
+            "length mismatch at first halfword {:#06x}",
+            first
+        );
+    }
+    check_decoded(decoded);
+}
+
+/// The same instructions are decoded with the same length by both specs,
+/// the output may differ, eg: `space` a harvard spec print the address
+/// space of the references, and `size` the 64 bits addresses.
+pub fn check_same_length<A, B>(big_endian: bool, parse_a: ParseFn<A>, parse_b: ParseFn<B>)
+where
+    A: TryFrom<u64> + Into<u64>,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+    B: TryFrom<u64> + Into<u64>,
+    <B as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let mut decoded = 0;
+    for first in 0..=u16::MAX {
+        let token = to_tokens(first, big_endian);
+        let a = decode(parse_a, &token, BASE_ADDR);
+        let b = decode(parse_b, &token, BASE_ADDR);
+        decoded += usize::from(b.is_some());
+        assert_eq!(
+            a.map(|(len, _)| len),
+            b.map(|(len, _)| len),
+            "length mismatch at first halfword {:#06x}",
+            first
+        );
+    }
+    check_decoded(decoded);
+}
+
+/// Specs that differ only outside the instruction decoding, eg: the stack
+/// direction, decode to exactly the same output.
+pub fn check_same_decoding<A>(big_endian: bool, parse_a: ParseFn<A>, parse_b: ParseFn<A>)
+where
+    A: TryFrom<u64> + Into<u64>,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let mut decoded = 0;
+    for first in 0..=u16::MAX {
+        let token = to_tokens(first, big_endian);
+        let a = decode(parse_a, &token, BASE_ADDR);
+        let b = decode(parse_b, &token, BASE_ADDR);
+        decoded += usize::from(b.is_some());
+        assert_eq!(a, b, "mismatch at first halfword {:#06x}", first);
+    }
+    check_decoded(decoded);
+}
+
+/// `size` instructions above the 32 bits address space decode with the same
+/// length as in the low addresses.
+pub const HIGH_ADDR: u64 = 0x1_0000_1000;
+pub fn check_high_addresses<A>(big_endian: bool, parse: ParseFn<A>)
+where
+    A: TryFrom<u64> + Into<u64>,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let mut decoded = 0;
+    for first in 0..=u16::MAX {
+        let token = to_tokens(first, big_endian);
+        let low = decode(parse, &token, BASE_ADDR);
+        let high = decode(parse, &token, HIGH_ADDR);
+        decoded += usize::from(low.is_some());
+        assert_eq!(
+            low.map(|(len, _)| len),
+            high.map(|(len, _)| len),
+            "length mismatch at first halfword {:#06x}",
+            first
+        );
+    }
+    check_decoded(decoded);
+}
+
+/// The toy corpora are split by feature, `assets/toy/<feature>.csv`, with
+/// the `addr`, `token` and `result` columns of [token_lib].
+pub fn toy_feature_file(feature: &str) -> String {
+    format!("../assets/toy/{}.csv", feature)
+}
+
+/// `address_unit` is the number of bytes in each address, 2 for the word
+/// size specs.
+pub fn tests_feature_from_file<A>(feature: &str, address_unit: u64, parse: ParseFn<A>)
+where
+    A: TryFrom<u64> + Into<u64> + core::fmt::Debug,
+    <A as TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    token_lib::tests_instruction_from_file(&toy_feature_file(feature), address_unit, parse)
+}
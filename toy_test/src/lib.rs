@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod test {
+    use toy_lib::*;
+
+    #[test]
+    fn endian_toy() {
+        check_endianness(toy_be::parse_default, toy_le::parse_default);
+    }
+    #[test]
+    fn endian_toy64() {
+        check_endianness(toy64_be::parse_default, toy64_le::parse_default);
+    }
+    #[test]
+    fn endian_builder() {
+        check_endianness(toy_builder_be::parse_default, toy_builder_le::parse_default);
+    }
+    #[test]
+    fn endian_builder_align2() {
+        check_endianness(
+            toy_builder_be_align2::parse_default,
+            toy_builder_le_align2::parse_default,
+        );
+    }
+    #[test]
+    fn endian_wordsize() {
+        check_endianness(toy_wsz_be::parse_default, toy_wsz_le::parse_default);
+    }
+    #[test]
+    fn endian_data_64() {
+        check_endianness(data_be_64::parse_default, data_le_64::parse_default);
+    }
+
+    #[test]
+    fn alignment_be() {
+        check_alignment(
+            2,
+            true,
+            toy_builder_be_align2::parse_default,
+            toy_builder_be::parse_default,
+        );
+    }
+    #[test]
+    fn alignment_le() {
+        check_alignment(
+            2,
+            false,
+            toy_builder_le_align2::parse_default,
+            toy_builder_le::parse_default,
+        );
+    }
+
+    #[test]
+    fn wordsize_be() {
+        check_wordsize(2, true, toy_wsz_be::parse_default, toy_be::parse_default);
+    }
+    #[test]
+    fn wordsize_le() {
+        check_wordsize(2, false, toy_wsz_le::parse_default, toy_le::parse_default);
+    }
+
+    #[test]
+    fn size_64() {
+        check_same_length(true, toy64_be::parse_default, toy_be::parse_default);
+        check_high_addresses(true, toy64_be::parse_default);
+        check_high_addresses(false, toy64_le::parse_default);
+    }
+
+    #[test]
+    fn space_harvard() {
+        check_same_length(
+            true,
+            toy64_be_harvard::parse_default,
+            toy64_be::parse_default,
+        );
+    }
+
+    #[test]
+    fn posstack() {
+        check_same_decoding(true, toy_be_posstack::parse_default, toy_be::parse_default);
+    }
+}
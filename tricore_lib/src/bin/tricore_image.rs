@@ -0,0 +1,40 @@
+//! Split the code of a real TriCore image in instruction tokens, to be
+//! disassembled by Ghidra into a corpus. The code is the raw `.text` section,
+//! extracted with `objcopy -O binary -j .text`, followed by its address:
+//!
+//! ```text
+//! cargo run -p tricore_lib --bin tricore_image text.bin 0x80000000 > tokens.csv
+//! analyzeHeadless /tmp corpus -scriptPath ghidra -preScript DisassembleCorpus.java \
+//!     tokens.csv assets/tricore/image.csv tricore:LE:32:default
+//! ```
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let (Some(file), Some(addr), None) = (args.next(), args.next(), args.next()) else {
+        panic!("usage: tricore_image <text.bin> <address>");
+    };
+    let code =
+        std::fs::read(&file).unwrap_or_else(|err| panic!("Unable to read {}: {}", file, err));
+    let mut addr = match addr.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).unwrap(),
+        None => addr.parse().unwrap(),
+    };
+
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record(["addr", "token"]).unwrap();
+    let mut rest = code.as_slice();
+    while rest.len() >= 2 {
+        // bit 0 of the first halfword selects the 32 bits instructions
+        let token = if rest[0] & 1 == 0 {
+            format!("{:04x}", u16::from_le_bytes([rest[0], rest[1]]))
+        } else if let Some(word) = rest.get(..4) {
+            format!("{:08x}", u32::from_le_bytes(word.try_into().unwrap()))
+        } else {
+            break;
+        };
+        writer.write_record([&addr.to_string(), &token]).unwrap();
+        let len = token.len() / 2;
+        addr += len as u32;
+        rest = &rest[len..];
+    }
+    writer.flush().unwrap();
+}
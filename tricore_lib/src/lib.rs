@@ -0,0 +1,76 @@
+use std::io::Read;
+
+use common_lib::test_token;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    /// Bit 0 of the first halfword is clear
+    HalfWord(u16),
+    Word(u32),
+}
+impl Token {
+    fn new(token: &str) -> Self {
+        match token.len() {
+            4 => Self::HalfWord(u16::from_str_radix(token, 16).unwrap()),
+            8 => Self::Word(u32::from_str_radix(token, 16).unwrap()),
+            _ => panic!("Invalid TriCore token `{}`", token),
+        }
+    }
+    pub fn to_tokens(&self) -> Vec<u8> {
+        match self {
+            Self::HalfWord(x) => x.to_le_bytes().to_vec(),
+            Self::Word(x) => x.to_le_bytes().to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub addr: u32,
+    pub token: Token,
+    pub result: String,
+}
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct InstructionSerialized {
+    addr: u32,
+    token: String,
+    result: String,
+}
+impl From<InstructionSerialized> for Instruction {
+    fn from(value: InstructionSerialized) -> Self {
+        Self {
+            addr: value.addr,
+            token: Token::new(&value.token),
+            result: value.result,
+        }
+    }
+}
+
+pub const BASIC_INSTRUCTION_FILE: &str = "../assets/tricore/basic.csv";
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
+    common_lib::tests_from_file::<InstructionSerialized, _, _>(file)
+}
+
+pub type ParseFn = common_lib::ParseFn<u32>;
+
+/// The length is selected by the first halfword, not by the available bytes,
+/// so each instruction is followed by a 32 bits instruction.
+const TRAILING_TOKEN: [u8; 4] = [0x6d, 0x00, 0x80, 0x00];
+
+pub fn tests_instruction_from_file(file: &str, parse: ParseFn) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        let token = instruction.token.to_tokens();
+        let addr = instruction.addr;
+        let buffer = [token.as_slice(), &TRAILING_TOKEN].concat();
+        let next_addr = test_token(&buffer, addr, &instruction.result, parse);
+        assert_eq!(
+            next_addr,
+            addr + token.len() as u32,
+            "at instruction {:x?}",
+            token
+        );
+    }
+}
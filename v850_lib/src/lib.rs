@@ -0,0 +1,70 @@
+use std::io::Read;
+
+use common_lib::test_token;
+use serde::Deserialize;
+
+#[derive(Debug, Clone)]
+pub enum Token {
+    Two(u16),
+    Four(u32),
+}
+impl Token {
+    fn to_tokens(&self) -> Vec<u8> {
+        match self {
+            Token::Two(x) => x.to_le_bytes().to_vec(),
+            Token::Four(x) => x.to_le_bytes().to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub addr: u32,
+    pub token: Token,
+    pub result: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum TokenType {
+    Two,
+    Four,
+}
+#[derive(Debug, Clone, Deserialize)]
+struct InstructionSerialized {
+    pub addr: u32,
+    pub token_value: u32,
+    pub token_type: TokenType,
+    pub result: String,
+}
+impl From<InstructionSerialized> for Instruction {
+    fn from(value: InstructionSerialized) -> Self {
+        let token = match value.token_type {
+            TokenType::Two => Token::Two(value.token_value.try_into().unwrap()),
+            TokenType::Four => Token::Four(value.token_value),
+        };
+        Self {
+            addr: value.addr,
+            token,
+            result: value.result,
+        }
+    }
+}
+
+pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
+    common_lib::tests_from_file::<InstructionSerialized, _, _>(file)
+}
+
+pub type ParseFn = common_lib::ParseFn<u32>;
+
+pub fn tests_instruction_from_file(file: &str, parse: ParseFn) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        let addr = instruction.addr;
+        let token = instruction.token.to_tokens();
+        let next_addr = test_token(&token, addr, &instruction.result, parse);
+        assert_eq!(next_addr, instruction.addr + token.len() as u32);
+    }
+}
+pub const RANDOM_INSTRUCTION_FILE: &str = "../assets/v850/random.csv";
+pub const RANDOM_BIG_INSTRUCTION_FILE: &str = "../assets/v850/random_big.csv";
@@ -1,7 +1,9 @@
+// Generated from matrix.toml, do not edit.
 #[cfg(test)]
 mod test {
     use x86_64::*;
     use x86_lib::*;
+
     #[test]
     fn strlen_32() {
         tests_instruction_from_file(STRLEN_32_INSTRUCTION_FILE, parse_64bits_emu32)
@@ -10,4 +12,13 @@ mod test {
     fn strlen_64() {
         tests_instruction_from_file(STRLEN_64_INSTRUCTION_FILE, parse_64bits)
     }
+    #[test]
+    fn encoding() {
+        let entry_points = EntryPoints {
+            parse_64bits: Some(parse_64bits),
+            parse_64bits_emu32: Some(parse_64bits_emu32),
+            ..Default::default()
+        };
+        tests_tagged_instruction_from_file(ENCODING_INSTRUCTION_FILE, entry_points)
+    }
 }
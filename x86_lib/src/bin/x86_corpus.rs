@@ -0,0 +1,233 @@
+//! Enumerate the tokens of the x86 encoding corpus, covering the legacy
+//! prefixes, REX, ModRM/SIB addressing, VEX/EVEX, x87 and 16 bits
+//! operand/address forms. The real mode boot code lives in `boot_sector.csv`
+//! instead.
+//!
+//! The expected disassembly is not produced here, it comes from Ghidra:
+//!
+//! ```text
+//! cargo run -p x86_lib --bin x86_corpus > tokens.csv
+//! analyzeHeadless /tmp corpus -scriptPath ghidra -preScript DisassembleCorpus.java \
+//!     tokens.csv assets/x86/encoding.csv \
+//!     "x16=x86:LE:16:Real Mode" x32=x86:LE:32:default \
+//!     emu32=x86:LE:64:compat32 x64=x86:LE:64:default
+//! ```
+use x86_lib::Mode;
+
+const MODE_32: &[Mode] = &[Mode::X32, Mode::Emu32];
+const MODE_64: &[Mode] = &[Mode::X64];
+const MODE_ALL: &[Mode] = &[Mode::X32, Mode::Emu32, Mode::X64];
+const MODE_ALL_16: &[Mode] = &[Mode::X16, Mode::X32, Mode::Emu32, Mode::X64];
+
+const DISP8: i8 = 0x10;
+const DISP8_NEG: i8 = -0x10;
+const DISP32: u32 = 0x1234;
+
+struct Corpus {
+    writer: csv::Writer<std::io::Stdout>,
+    addr: u64,
+}
+
+impl Corpus {
+    fn row(&mut self, modes: &[Mode], token: &[u8]) {
+        let modes: Vec<_> = modes.iter().map(Mode::name).collect();
+        let token: String = token.iter().map(|byte| format!("{:02x}", byte)).collect();
+        self.writer
+            .write_record([&self.addr.to_string(), &modes.join(" "), &token])
+            .unwrap();
+        self.addr += token.len() as u64 / 2;
+    }
+}
+
+/// ModRM (and SIB) bytes for the `mod` and `rm`, with a SIB if needed, the
+/// reg field is derived from them to vary the destination register.
+fn addressing(mode: u8, rm: u8, sib: Option<(u8, u8, u8)>) -> Vec<u8> {
+    let reg_field = (rm + mode) & 7;
+    let mut bytes = vec![mode << 6 | reg_field << 3 | rm];
+    let mut no_base = rm == 5 && mode == 0;
+    if rm == 4 {
+        let (scale, index, base) = sib.unwrap();
+        bytes.push(scale << 6 | index << 3 | base);
+        no_base = base == 5 && mode == 0;
+    }
+    match mode {
+        0 if no_base => bytes.extend(DISP32.to_le_bytes()),
+        0 => {}
+        1 => {
+            let disp = if rm & 1 == 0 { DISP8 } else { DISP8_NEG };
+            bytes.push(disp as u8);
+        }
+        2 => bytes.extend(DISP32.to_le_bytes()),
+        _ => unreachable!(),
+    }
+    bytes
+}
+
+/// `MOV r, r/m` for all the mod/rm combinations and SIB base/index pairs.
+fn modrm_32(corpus: &mut Corpus) {
+    for mode in 0..3 {
+        for rm in 0..8 {
+            let sibs: Vec<_> = if rm == 4 {
+                (0..8)
+                    .flat_map(|index| (0..8).map(move |base| ((index + base) % 4, index, base)))
+                    .map(Some)
+                    .collect()
+            } else {
+                vec![None]
+            };
+            for sib in sibs {
+                let mut token = vec![0x8b];
+                token.extend(addressing(mode, rm, sib));
+                corpus.row(MODE_32, &token);
+            }
+        }
+    }
+    for rm in 0..8 {
+        corpus.row(MODE_ALL, &[0x8b, 0xc0 | ((rm + 1) % 8) << 3 | rm]);
+    }
+}
+
+fn modrm_64(corpus: &mut Corpus) {
+    for rex in 0x48..=0x4f {
+        for mode in 0..3 {
+            for rm in 0..8 {
+                let sibs: Vec<_> = if rm == 4 {
+                    (0..8)
+                        .filter(|index| (index + mode + rex) % 3 == 0)
+                        .flat_map(|index| (0..8).map(move |base| ((index + base) % 4, index, base)))
+                        .map(Some)
+                        .collect()
+                } else {
+                    vec![None]
+                };
+                for sib in sibs {
+                    let mut token = vec![rex, 0x8b];
+                    token.extend(addressing(mode, rm, sib));
+                    corpus.row(MODE_64, &token);
+                }
+            }
+        }
+        for rm in 0..8 {
+            let reg = (rm + 3) % 8;
+            corpus.row(MODE_64, &[rex, 0x8b, 0xc0 | reg << 3 | rm]);
+        }
+    }
+    // without REX.W the operand is 32 bits, REX.R/B extend to R8D-R15D
+    for rex in [None, Some(0x41), Some(0x44), Some(0x45)] {
+        for rm in [0, 1, 3, 6, 7] {
+            let mut token: Vec<u8> = rex.into_iter().collect();
+            token.extend([0x8b, 2 << 3 | rm]);
+            corpus.row(MODE_64, &token);
+        }
+    }
+    // with a REX prefix the byte registers 4-7 are SPL, BPL, SIL and DIL
+    for rm in 4..8 {
+        corpus.row(MODE_64, &[0x88, 0xc0 | rm]);
+        corpus.row(MODE_64, &[0x40, 0x88, 0xc0 | rm]);
+        corpus.row(MODE_64, &[0x41, 0x88, 0xc0 | rm]);
+    }
+}
+
+fn prefixes(corpus: &mut Corpus) {
+    // operand size
+    for rm in [0, 1, 3, 6, 7] {
+        corpus.row(MODE_32, &[0x66, 0x8b, 0x08 | rm]);
+        corpus.row(MODE_64, &[0x66, 0x8b, 0x08 | rm]);
+        corpus.row(MODE_ALL, &[0x66, 0x8b, 0xc8 | rm]);
+    }
+    // address size, 16 bits addressing on 32 bits mode
+    for mode in 0..3u8 {
+        for rm in 0..8u8 {
+            let mut token = vec![0x67, 0x8b, mode << 6 | rm];
+            match mode {
+                0 if rm == 6 => token.extend(0x1234u16.to_le_bytes()),
+                0 => {}
+                1 => {
+                    let disp = if rm & 1 == 0 { DISP8 } else { DISP8_NEG };
+                    token.push(disp as u8);
+                }
+                _ => token.extend(0x1234u16.to_le_bytes()),
+            }
+            corpus.row(MODE_32, &token);
+        }
+    }
+    // address size, 32 bits addressing on 64 bits mode
+    for rm in [0, 1, 3, 6, 7] {
+        corpus.row(MODE_64, &[0x67, 0x8b, rm]);
+    }
+    // segment override, ES, CS, SS, DS, FS and GS
+    for prefix in [0x26, 0x2e, 0x36, 0x3e, 0x64, 0x65] {
+        corpus.row(MODE_32, &[prefix, 0x8b, 0x03]);
+        if matches!(prefix, 0x64 | 0x65) {
+            corpus.row(MODE_64, &[prefix, 0x48, 0x8b, 0x03]);
+        }
+    }
+    // repeat and lock
+    corpus.row(MODE_32, &[0xf3, 0xa4]);
+    corpus.row(MODE_32, &[0xf3, 0xa5]);
+    corpus.row(MODE_32, &[0xf3, 0xaa]);
+    corpus.row(MODE_32, &[0xf3, 0xab]);
+    corpus.row(MODE_32, &[0xf2, 0xae]);
+    corpus.row(MODE_64, &[0xf3, 0xa4]);
+    corpus.row(MODE_64, &[0xf3, 0x48, 0xa5]);
+    corpus.row(MODE_64, &[0xf3, 0x48, 0xab]);
+    corpus.row(MODE_32, &[0xf0, 0x01, 0x08]);
+    corpus.row(MODE_32, &[0xf0, 0x0f, 0xb1, 0x11]);
+    corpus.row(MODE_64, &[0xf0, 0x48, 0x0f, 0xb1, 0x11]);
+    corpus.row(MODE_64, &[0xf0, 0xff, 0x00]);
+    // mandatory prefixes
+    corpus.row(MODE_ALL, &[0x0f, 0x10, 0xc1]);
+    corpus.row(MODE_ALL, &[0x66, 0x0f, 0x10, 0xc1]);
+    corpus.row(MODE_ALL, &[0xf3, 0x0f, 0x10, 0xc1]);
+    corpus.row(MODE_ALL, &[0xf2, 0x0f, 0x10, 0xc1]);
+    corpus.row(MODE_ALL, &[0x66, 0x0f, 0x6f, 0xc1]);
+    corpus.row(MODE_ALL, &[0xf3, 0x0f, 0x6f, 0xc1]);
+    corpus.row(MODE_64, &[0x66, 0x45, 0x0f, 0x6f, 0xc1]);
+}
+
+fn vex(corpus: &mut Corpus) {
+    corpus.row(MODE_ALL, &[0xc5, 0xf8, 0x57, 0xc0]);
+    corpus.row(MODE_ALL, &[0xc5, 0xf4, 0x58, 0xc2]);
+    corpus.row(MODE_ALL, &[0xc5, 0xf5, 0x58, 0xc2]);
+    corpus.row(MODE_ALL, &[0xc5, 0xf6, 0x58, 0xc2]);
+    corpus.row(MODE_ALL, &[0xc5, 0xf7, 0x58, 0xc2]);
+    corpus.row(MODE_32, &[0xc5, 0xfe, 0x6f, 0x00]);
+    corpus.row(MODE_64, &[0xc5, 0xfe, 0x6f, 0x00]);
+    corpus.row(MODE_64, &[0xc4, 0xe2, 0x7d, 0x18, 0x00]);
+    corpus.row(MODE_64, &[0xc4, 0x41, 0x34, 0x58, 0xc2]);
+    // EVEX
+    corpus.row(MODE_64, &[0x62, 0xf1, 0x74, 0x48, 0x58, 0xc2]);
+    corpus.row(MODE_64, &[0x62, 0xf1, 0xf5, 0x48, 0x58, 0xc2]);
+    corpus.row(MODE_64, &[0x62, 0xf1, 0x7c, 0x48, 0x28, 0xc1]);
+}
+
+fn x87(corpus: &mut Corpus) {
+    corpus.row(MODE_ALL_16, &[0xd9, 0xee]);
+    corpus.row(MODE_ALL_16, &[0xd9, 0xe8]);
+    corpus.row(MODE_ALL_16, &[0xd9, 0xeb]);
+    corpus.row(MODE_ALL_16, &[0xd9, 0xe0]);
+    corpus.row(MODE_ALL_16, &[0xd9, 0xe1]);
+    corpus.row(MODE_ALL_16, &[0xd9, 0xfa]);
+    corpus.row(&[Mode::X16], &[0xd9, 0x07]);
+    corpus.row(MODE_32, &[0xd9, 0x00]);
+    corpus.row(MODE_32, &[0xdd, 0x1c, 0x24]);
+    corpus.row(MODE_32, &[0xdb, 0x45, 0xfc]);
+    corpus.row(MODE_64, &[0xd9, 0x00]);
+    corpus.row(MODE_64, &[0xdd, 0x1c, 0x24]);
+    corpus.row(MODE_64, &[0xdb, 0x45, 0xfc]);
+}
+
+fn main() {
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record(["addr", "modes", "token"]).unwrap();
+    let mut corpus = Corpus {
+        writer,
+        addr: 0x1000,
+    };
+    modrm_32(&mut corpus);
+    modrm_64(&mut corpus);
+    prefixes(&mut corpus);
+    vex(&mut corpus);
+    x87(&mut corpus);
+    corpus.writer.flush().unwrap();
+}
@@ -14,19 +14,20 @@ struct InstructionSerialized {
     pub token: String,
     pub result: String,
 }
+fn token_from_hex(token: &str) -> Vec<u8> {
+    //TODO make this happen inside the deserializer
+    token
+        .as_bytes()
+        .chunks(2)
+        .map(String::from_utf8_lossy) //I'm lazy
+        .map(|byte| u8::from_str_radix(&byte, 16).unwrap())
+        .collect()
+}
 impl From<InstructionSerialized> for Instruction {
     fn from(value: InstructionSerialized) -> Self {
-        //TODO make this happen inside the deserializer
-        let token = value
-            .token
-            .as_bytes()
-            .chunks(2)
-            .map(String::from_utf8_lossy) //I'm lazy
-            .map(|byte| u8::from_str_radix(&byte, 16).unwrap())
-            .collect();
         Self {
             addr: value.addr,
-            token,
+            token: token_from_hex(&value.token),
             result: value.result,
         }
     }
@@ -40,13 +41,106 @@ impl<R: Read> Iterator for TestsFromFile<R> {
     }
 }
 
+/// The decoder entry point that a row applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// `parse_16bits` from the x86 crate
+    X16,
+    /// `parse_32bits` from the x86 crate
+    X32,
+    /// `parse_64bits_emu32` from the x86_64 crate
+    Emu32,
+    /// `parse_64bits` from the x86_64 crate
+    X64,
+}
+impl Mode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mode::X16 => "x16",
+            Mode::X32 => "x32",
+            Mode::Emu32 => "emu32",
+            Mode::X64 => "x64",
+        }
+    }
+    fn from_name(name: &str) -> Self {
+        match name {
+            "x16" => Mode::X16,
+            "x32" => Mode::X32,
+            "emu32" => Mode::Emu32,
+            "x64" => Mode::X64,
+            _ => panic!("Invalid x86 mode `{}`", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TaggedInstruction {
+    pub modes: Vec<Mode>,
+    pub instruction: Instruction,
+}
+#[derive(Debug, Clone, Deserialize)]
+struct TaggedInstructionSerialized {
+    pub addr: u64,
+    pub modes: String,
+    pub token: String,
+    pub result: String,
+}
+impl From<TaggedInstructionSerialized> for TaggedInstruction {
+    fn from(value: TaggedInstructionSerialized) -> Self {
+        Self {
+            modes: value
+                .modes
+                .split_whitespace()
+                .map(Mode::from_name)
+                .collect(),
+            instruction: Instruction {
+                addr: value.addr,
+                token: token_from_hex(&value.token),
+                result: value.result,
+            },
+        }
+    }
+}
+
 pub const STRLEN_32_INSTRUCTION_FILE: &str = "../assets/x86/strlen_32.csv";
 pub const STRLEN_64_INSTRUCTION_FILE: &str = "../assets/x86/strlen_64.csv";
+pub const ENCODING_INSTRUCTION_FILE: &str = "../assets/x86/encoding.csv";
+pub const BOOT_SECTOR_INSTRUCTION_FILE: &str = "../assets/x86/boot_sector.csv";
 pub fn tests_from_file<R: Read>(file: R) -> impl Iterator<Item = csv::Result<Instruction>> {
     TestsFromFile(csv::Reader::from_reader(file).into_deserialize::<InstructionSerialized>())
 }
+pub fn tagged_tests_from_file<R: Read>(
+    file: R,
+) -> impl Iterator<Item = csv::Result<TaggedInstruction>> {
+    csv::Reader::from_reader(file)
+        .into_deserialize::<TaggedInstructionSerialized>()
+        .map(|test| test.map(TaggedInstruction::from))
+}
+
+pub type ParseFn<A> = fn(&[u8], A) -> Option<(A, String)>;
+
+fn test_instruction<A>(instruction: &Instruction, parse: ParseFn<A>)
+where
+    A: TryFrom<u64> + TryInto<u64> + core::fmt::Debug + std::ops::Add,
+    u64: TryFrom<A>,
+    <A as TryFrom<u64>>::Error: std::fmt::Debug,
+    <u64 as TryFrom<A>>::Error: std::fmt::Debug,
+{
+    let addr = A::try_from(instruction.addr).unwrap();
+    let Some((next_addr, result)) = parse(&instruction.token, addr) else {
+        panic!(
+            "Unable to parse the {:x?} with expected output `{}`",
+            &instruction.token, &instruction.result,
+        );
+    };
+    assert_eq!(result, instruction.result);
+    assert_eq!(
+        u64::try_from(next_addr).unwrap(),
+        instruction.addr + instruction.token.len() as u64
+    );
+}
 
-pub fn tests_instruction_from_file<A>(file: &str, parse: fn(&[u8], A) -> Option<(A, String)>)
+pub fn tests_instruction_from_file<A>(file: &str, parse: ParseFn<A>)
 where
     A: TryFrom<u64> + TryInto<u64> + core::fmt::Debug + std::ops::Add,
     u64: TryFrom<A>,
@@ -56,18 +150,117 @@ where
     let test_file = std::fs::File::open(file).unwrap();
     let instructions = tests_from_file(test_file);
     for instruction in instructions.map(Result::unwrap) {
-        let addr = A::try_from(instruction.addr).unwrap();
-        let Some((next_addr, result)) = parse(&instruction.token, addr) else {
-                panic!(
-                    "Unable to parse the {:x?} with expected output `{}`",
-                    &instruction.token,
-                    &instruction.result,
-                );
-            };
+        test_instruction(&instruction, parse);
+    }
+}
+
+/// The entry points available in the crate been tested, rows are only tested
+/// in the modes that the crate implements.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EntryPoints {
+    pub parse_16bits: Option<ParseFn<u32>>,
+    pub parse_32bits: Option<ParseFn<u32>>,
+    pub parse_64bits: Option<ParseFn<u64>>,
+    pub parse_64bits_emu32: Option<ParseFn<u64>>,
+}
+
+pub fn tests_tagged_instruction_from_file(file: &str, entry_points: EntryPoints) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let tests = tagged_tests_from_file(test_file);
+    for test in tests.map(Result::unwrap) {
+        for mode in test.modes.iter() {
+            match mode {
+                Mode::X16 => {
+                    if let Some(parse) = entry_points.parse_16bits {
+                        test_instruction(&test.instruction, parse);
+                    }
+                }
+                Mode::X32 => {
+                    if let Some(parse) = entry_points.parse_32bits {
+                        test_instruction(&test.instruction, parse);
+                    }
+                }
+                Mode::Emu32 => {
+                    if let Some(parse) = entry_points.parse_64bits_emu32 {
+                        test_instruction(&test.instruction, parse);
+                    }
+                }
+                Mode::X64 => {
+                    if let Some(parse) = entry_points.parse_64bits {
+                        test_instruction(&test.instruction, parse);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Real mode `segment:offset` address, the decoder works with the linear
+/// address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RealModeAddr {
+    pub segment: u16,
+    pub offset: u16,
+}
+impl RealModeAddr {
+    pub fn linear(&self) -> u32 {
+        ((self.segment as u32) << 4) + self.offset as u32
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RealModeInstruction {
+    pub addr: RealModeAddr,
+    pub token: Vec<u8>,
+    pub result: String,
+}
+#[derive(Debug, Clone, Deserialize)]
+struct RealModeInstructionSerialized {
+    pub segment: u16,
+    pub offset: u16,
+    pub token: String,
+    pub result: String,
+}
+impl From<RealModeInstructionSerialized> for RealModeInstruction {
+    fn from(value: RealModeInstructionSerialized) -> Self {
+        Self {
+            addr: RealModeAddr {
+                segment: value.segment,
+                offset: value.offset,
+            },
+            token: token_from_hex(&value.token),
+            result: value.result,
+        }
+    }
+}
+pub fn real_mode_tests_from_file<R: Read>(
+    file: R,
+) -> impl Iterator<Item = csv::Result<RealModeInstruction>> {
+    csv::Reader::from_reader(file)
+        .into_deserialize::<RealModeInstructionSerialized>()
+        .map(|test| test.map(RealModeInstruction::from))
+}
+
+pub fn tests_real_mode_instruction_from_file(file: &str, parse: ParseFn<u32>) {
+    let test_file = std::fs::File::open(file).unwrap();
+    let instructions = real_mode_tests_from_file(test_file);
+    for instruction in instructions.map(Result::unwrap) {
+        let addr = instruction.addr;
+        let Some((next_addr, result)) = parse(&instruction.token, addr.linear()) else {
+            panic!(
+                "Unable to parse the {:x?} at {:04x}:{:04x} with expected output `{}`",
+                &instruction.token, addr.segment, addr.offset, &instruction.result,
+            );
+        };
         assert_eq!(result, instruction.result);
+        // the decoder only sees the linear address, the offset doesn't wrap
+        // inside the segment
         assert_eq!(
-            u64::try_from(next_addr).unwrap(),
-            instruction.addr + instruction.token.len() as u64
+            next_addr,
+            addr.linear() + instruction.token.len() as u32,
+            "next instruction of {:04x}:{:04x}",
+            addr.segment,
+            addr.offset
         );
     }
 }
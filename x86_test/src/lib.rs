@@ -1,3 +1,4 @@
+// Generated from matrix.toml, do not edit.
 #[cfg(test)]
 mod test {
     use x86::*;
@@ -7,4 +8,17 @@ mod test {
     fn strlen() {
         tests_instruction_from_file::<u32>(STRLEN_32_INSTRUCTION_FILE, parse_32bits)
     }
+    #[test]
+    fn encoding() {
+        let entry_points = EntryPoints {
+            parse_16bits: Some(parse_16bits),
+            parse_32bits: Some(parse_32bits),
+            ..Default::default()
+        };
+        tests_tagged_instruction_from_file(ENCODING_INSTRUCTION_FILE, entry_points)
+    }
+    #[test]
+    fn boot_sector() {
+        tests_real_mode_instruction_from_file(BOOT_SECTOR_INSTRUCTION_FILE, parse_16bits)
+    }
 }